@@ -0,0 +1,346 @@
+use crate::error::Error;
+use crate::progress::{Progress, ProgressPhase, ProgressReporter};
+use rootcause::prelude::ResultExt;
+use rootcause::report;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::{Component, Path};
+
+/// Unpacks a downloaded Chrome for Testing zip archive into `dir`.
+///
+/// Reports [`ProgressPhase::Extracting`] updates to `progress` once before the first entry and
+/// after every unpacked entry, counting uncompressed bytes. File permissions and symbolic links
+/// (as used inside macOS app bundles) are restored on Unix. Returns the number of unpacked
+/// bytes.
+///
+/// Symbolic links must point to a location inside `dir`, and no entry may be written through a
+/// symbolic link unpacked before it.
+///
+/// Useful on its own when downloading through an HTTP backend other than `reqwest`; see
+/// `Download::install` of the `reqwest` feature otherwise.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArchive`] if `archive` is no valid zip archive, contains paths or
+/// symbolic links escaping `dir` or contains symbolic links on a system other than Unix, or an
+/// error if writing to `dir` fails.
+pub fn extract_archive(
+    archive: impl Read + Seek,
+    dir: &Path,
+    progress: &impl ProgressReporter,
+) -> crate::Result<u64> {
+    let mut archive = zip::ZipArchive::new(archive).map_err(invalid_archive)?;
+
+    let mut total_bytes = 0;
+    for index in 0..archive.len() {
+        total_bytes += archive.by_index(index).map_err(invalid_archive)?.size();
+    }
+    let report = |bytes_processed| {
+        progress.report(Progress {
+            phase: ProgressPhase::Extracting,
+            bytes_processed,
+            total_bytes: Some(total_bytes),
+        });
+    };
+
+    let mut bytes_processed = 0;
+    report(bytes_processed);
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(invalid_archive)?;
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(report!(Error::InvalidArchive {
+                reason: format!("entry '{}' escapes the target directory", entry.name()),
+            }));
+        };
+        if let Some(link) = relative
+            .ancestors()
+            .filter(|ancestor| !ancestor.as_os_str().is_empty())
+            .find(|ancestor| dir.join(ancestor).is_symlink())
+        {
+            return Err(report!(Error::InvalidArchive {
+                reason: format!(
+                    "entry '{}' is written through symbolic link '{}'",
+                    entry.name(),
+                    link.display()
+                ),
+            }));
+        }
+        let path = dir.join(&relative);
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path)
+                .context_to::<Error>()
+                .attach_with(|| format!("while creating '{}'", path.display()))?;
+        } else {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .context_to::<Error>()
+                    .attach_with(|| format!("while creating '{}'", parent.display()))?;
+            }
+            if entry.is_symlink() {
+                let mut target = String::new();
+                entry
+                    .read_to_string(&mut target)
+                    .context_to::<Error>()
+                    .attach_with(|| format!("while unpacking '{}'", entry.name()))?;
+                if !stays_enclosed(&relative, Path::new(&target)) {
+                    return Err(report!(Error::InvalidArchive {
+                        reason: format!(
+                            "symbolic link '{}' points to '{target}' outside the target directory",
+                            entry.name()
+                        ),
+                    }));
+                }
+                create_symlink(&target, &path)?;
+            } else {
+                let mut file = File::create(&path)
+                    .context_to::<Error>()
+                    .attach_with(|| format!("while creating '{}'", path.display()))?;
+                std::io::copy(&mut entry, &mut file)
+                    .context_to::<Error>()
+                    .attach_with(|| format!("while unpacking '{}'", entry.name()))?;
+            }
+        }
+
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode()
+            && !entry.is_symlink()
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode & 0o7777))
+                .context_to::<Error>()
+                .attach_with(|| format!("while setting permissions of '{}'", path.display()))?;
+        }
+
+        bytes_processed += entry.size();
+        report(bytes_processed);
+    }
+
+    Ok(bytes_processed)
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &str, path: &Path) -> crate::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+        .context_to::<Error>()
+        .attach_with(|| format!("while creating symbolic link '{}'", path.display()))
+}
+
+/// Symbolic links only occur in macOS archives, which are of no use on other systems anyway.
+#[cfg(not(unix))]
+fn create_symlink(_target: &str, path: &Path) -> crate::Result<()> {
+    Err(report!(Error::InvalidArchive {
+        reason: format!(
+            "symbolic link '{}' cannot be restored on this system",
+            path.display()
+        ),
+    }))
+}
+
+/// Whether the symbolic link `link` (relative to the target directory) pointing to `target`
+/// resolves to a location inside the target directory, judged from the paths alone.
+fn stays_enclosed(link: &Path, target: &Path) -> bool {
+    let parent = link.parent().unwrap_or(Path::new(""));
+    let mut depth = 0_usize;
+    for component in parent.components().chain(target.components()) {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => match depth.checked_sub(1) {
+                Some(parent) => depth = parent,
+                None => return false,
+            },
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
+fn invalid_archive(error: zip::result::ZipError) -> rootcause::Report<Error> {
+    report!(Error::InvalidArchive {
+        reason: error.to_string(),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::progress::NoProgress;
+    use assertr::prelude::*;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    /// A zip archive resembling a Chrome for Testing download.
+    pub(crate) fn chrome_archive() -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .add_directory("chrome-linux64/", SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .start_file(
+                "chrome-linux64/chrome",
+                SimpleFileOptions::default().unix_permissions(0o755),
+            )
+            .unwrap();
+        writer.write_all(b"#!/bin/sh\necho chrome\n").unwrap();
+        writer
+            .start_file(
+                "chrome-linux64/deb.deps",
+                SimpleFileOptions::default().unix_permissions(0o644),
+            )
+            .unwrap();
+        writer.write_all(b"libnss3\n").unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn extracts_entries_and_reports_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let updates = std::cell::RefCell::new(Vec::new());
+
+        let bytes = extract_archive(Cursor::new(chrome_archive()), dir.path(), &|progress| {
+            updates.borrow_mut().push(progress);
+        })
+        .unwrap();
+
+        let chrome = dir.path().join("chrome-linux64/chrome");
+        assert_that!(std::fs::read_to_string(&chrome).unwrap())
+            .is_equal_to(String::from("#!/bin/sh\necho chrome\n"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&chrome).unwrap().permissions().mode();
+            assert_that!(mode & 0o777).is_equal_to(0o755);
+        }
+        let updates = updates.into_inner();
+        assert_that!(updates.first().copied()).is_equal_to(Some(Progress {
+            phase: ProgressPhase::Extracting,
+            bytes_processed: 0,
+            total_bytes: Some(bytes),
+        }));
+        assert_that!(updates.last().copied()).is_equal_to(Some(Progress {
+            phase: ProgressPhase::Extracting,
+            bytes_processed: bytes,
+            total_bytes: Some(bytes),
+        }));
+    }
+
+    #[test]
+    fn rejects_invalid_archives() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let err =
+            extract_archive(Cursor::new(b"no zip".to_vec()), dir.path(), &NoProgress).unwrap_err();
+
+        let Error::InvalidArchive { .. } = err.current_context() else {
+            panic!(
+                "expected invalid archive error, got: {:?}",
+                err.current_context()
+            );
+        };
+    }
+
+    #[test]
+    fn rejects_entries_escaping_target_directory() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("../escaped", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"escaped").unwrap();
+        let archive = writer.finish().unwrap().into_inner();
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+
+        let err = extract_archive(Cursor::new(archive), &target, &NoProgress).unwrap_err();
+
+        let Error::InvalidArchive { reason } = err.current_context() else {
+            panic!(
+                "expected invalid archive error, got: {:?}",
+                err.current_context()
+            );
+        };
+        assert_that!(reason.as_str()).contains("escapes the target directory");
+        assert_that!(dir.path().join("escaped").exists()).is_false();
+    }
+
+    fn archive_with_symlink(link: &str, target: &str, file: &str) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .add_symlink(link, target, SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .start_file(file, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"escaped").unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn assert_rejected(archive: Vec<u8>, expected_reason: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+
+        let err = extract_archive(Cursor::new(archive), &target, &NoProgress).unwrap_err();
+
+        let Error::InvalidArchive { reason } = err.current_context() else {
+            panic!(
+                "expected invalid archive error, got: {:?}",
+                err.current_context()
+            );
+        };
+        assert_that!(reason.as_str()).contains(expected_reason);
+        assert_that!(dir.path().join("escaped").exists()).is_false();
+    }
+
+    #[test]
+    fn rejects_symlinks_to_absolute_paths() {
+        assert_rejected(
+            archive_with_symlink("link", "/", "link/escaped"),
+            "outside the target directory",
+        );
+    }
+
+    #[test]
+    fn rejects_symlinks_escaping_target_directory() {
+        assert_rejected(
+            archive_with_symlink("nested/link", "../..", "nested/link/escaped"),
+            "outside the target directory",
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_entries_written_through_symlinks() {
+        assert_rejected(
+            archive_with_symlink("link", "inner", "link/escaped"),
+            "is written through symbolic link 'link'",
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restores_symlinks_inside_target_directory() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("Versions/1/Framework", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"framework").unwrap();
+        writer
+            .add_symlink("Versions/Current", "1", SimpleFileOptions::default())
+            .unwrap();
+        writer
+            .add_symlink(
+                "Framework",
+                "Versions/Current/Framework",
+                SimpleFileOptions::default(),
+            )
+            .unwrap();
+        let archive = writer.finish().unwrap().into_inner();
+        let dir = tempfile::tempdir().unwrap();
+
+        extract_archive(Cursor::new(archive), dir.path(), &NoProgress).unwrap();
+
+        assert_that!(std::fs::read_to_string(dir.path().join("Framework")).unwrap())
+            .is_equal_to(String::from("framework"));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::api::platform::Platform;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// A binary distributed by Chrome for Testing.
+///
+/// Serialized using the asset names of the `downloads` objects in API responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binary {
+    /// The Chrome browser.
+    #[serde(rename = "chrome")]
+    Chrome,
+
+    /// The `ChromeDriver` WebDriver server.
+    #[serde(rename = "chromedriver")]
+    ChromeDriver,
+
+    /// The Chrome Headless Shell, a lightweight headless-only browser build.
+    #[serde(rename = "chrome-headless-shell")]
+    ChromeHeadlessShell,
+}
+
+impl Display for Binary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Binary::Chrome => "chrome",
+            Binary::ChromeDriver => "chromedriver",
+            Binary::ChromeHeadlessShell => "chrome-headless-shell",
+        })
+    }
+}
+
+impl Binary {
+    /// Filename of this binary's executable on the given platform.
+    #[must_use]
+    pub fn executable_name(self, platform: Platform) -> &'static str {
+        match self {
+            Binary::Chrome => platform.chrome_executable_name(),
+            Binary::ChromeDriver => platform.chromedriver_executable_name(),
+            Binary::ChromeHeadlessShell => platform.chrome_headless_shell_executable_name(),
+        }
+    }
+
+    /// Relative path of this binary's executable inside its unpacked archive for the given
+    /// platform.
+    #[must_use]
+    pub fn executable_path(self, platform: Platform) -> &'static Path {
+        match self {
+            Binary::Chrome => platform.chrome_executable_path(),
+            Binary::ChromeDriver => platform.chromedriver_executable_path(),
+            Binary::ChromeHeadlessShell => platform.chrome_headless_shell_executable_path(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn serialized_value_matches_display_output() {
+        for binary in [
+            Binary::Chrome,
+            Binary::ChromeDriver,
+            Binary::ChromeHeadlessShell,
+        ] {
+            assert_that!(serde_json::to_string(&binary).unwrap())
+                .is_equal_to(format!("\"{binary}\""));
+        }
+    }
+
+    #[test]
+    fn executable_path_matches_platform_helpers() {
+        assert_that!(Binary::ChromeDriver.executable_path(Platform::Win64))
+            .is_equal_to(Platform::Win64.chromedriver_executable_path());
+        assert_that!(Binary::ChromeHeadlessShell.executable_name(Platform::MacArm64))
+            .is_equal_to("chrome-headless-shell");
+    }
+}
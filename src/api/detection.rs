@@ -0,0 +1,344 @@
+use crate::api::platform::Platform;
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use std::borrow::Cow;
+use std::env::consts;
+use std::fmt::{Display, Formatter};
+use std::process::Command;
+
+/// The environment variable consulted by default to override platform detection.
+pub const PLATFORM_OVERRIDE_ENV_VAR: &str = "CHROME_FOR_TESTING_PLATFORM";
+
+/// Options for [`Platform::detect_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectOptions {
+    /// Prefer the build matching the host hardware over the one matching the current process.
+    ///
+    /// This selects `mac-arm64` for `x86_64` processes translated by Rosetta 2, and `win64` for
+    /// 32-bit processes running under WOW64 on 64-bit Windows. Defaults to `true`.
+    pub prefer_native: bool,
+
+    /// Name of an environment variable which, if set to a platform name like `linux64`, takes
+    /// precedence over any detection. Defaults to [`PLATFORM_OVERRIDE_ENV_VAR`].
+    pub override_env_var: Option<Cow<'static, str>>,
+}
+
+impl Default for DetectOptions {
+    fn default() -> Self {
+        Self {
+            prefer_native: true,
+            override_env_var: Some(Cow::Borrowed(PLATFORM_OVERRIDE_ENV_VAR)),
+        }
+    }
+}
+
+/// How a [`DetectedPlatform`] was determined.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DetectionSource {
+    /// Read from the override environment variable.
+    Override {
+        /// Name of the environment variable.
+        env_var: String,
+    },
+
+    /// Derived from the OS and architecture this process was compiled for.
+    CompileTarget,
+
+    /// The process is an `x86_64` build translated by Rosetta 2 on an Apple Silicon host.
+    Rosetta,
+
+    /// The process is a 32-bit build running under WOW64 on a 64-bit Windows host.
+    Wow64,
+}
+
+/// The result of [`Platform::detect_with`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DetectedPlatform {
+    /// The detected platform.
+    pub platform: Platform,
+
+    /// How the platform was determined.
+    pub source: DetectionSource,
+
+    /// The operating system this process was compiled for, e.g. "macos".
+    pub os: &'static str,
+
+    /// The architecture this process was compiled for, e.g. "`x86_64`".
+    pub arch: &'static str,
+}
+
+impl Display for DetectedPlatform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            platform, os, arch, ..
+        } = self;
+        match &self.source {
+            DetectionSource::Override { env_var } => {
+                write!(
+                    f,
+                    "{platform} (set through the {env_var} environment variable)"
+                )
+            }
+            DetectionSource::CompileTarget => {
+                write!(
+                    f,
+                    "{platform} (process compiled for os: {os}, arch: {arch})"
+                )
+            }
+            DetectionSource::Rosetta => write!(
+                f,
+                "{platform} (process compiled for os: {os}, arch: {arch}, translated by Rosetta 2 on an arm64 host)"
+            ),
+            DetectionSource::Wow64 => write!(
+                f,
+                "{platform} (process compiled for os: {os}, arch: {arch}, running under WOW64 on a 64-bit host)"
+            ),
+        }
+    }
+}
+
+/// Information about the host, abstracted for testability.
+trait Host {
+    fn os(&self) -> &'static str;
+    fn arch(&self) -> &'static str;
+    fn env_var(&self, name: &str) -> Option<String>;
+
+    /// Whether this process is translated by Rosetta 2.
+    fn is_rosetta_translated(&self) -> bool;
+}
+
+struct CurrentHost;
+
+impl Host for CurrentHost {
+    fn os(&self) -> &'static str {
+        consts::OS
+    }
+
+    fn arch(&self) -> &'static str {
+        consts::ARCH
+    }
+
+    fn env_var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok()
+    }
+
+    fn is_rosetta_translated(&self) -> bool {
+        Command::new("sysctl")
+            .args(["-n", "sysctl.proc_translated"])
+            .output()
+            .is_ok_and(|output| output.status.success() && output.stdout.trim_ascii() == b"1")
+    }
+}
+
+impl Platform {
+    /// Detect the platform identifier that should be used for the current system, taking
+    /// emulation layers and an override environment variable into account.
+    ///
+    /// Unlike [`Platform::detect`], which only considers the target this process was compiled
+    /// for, this can pick the native `mac-arm64` build for an `x86_64` process running under
+    /// Rosetta 2, or the `win64` build for a 32-bit process on 64-bit Windows. The returned
+    /// [`DetectedPlatform`] explains how the platform was determined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the override environment variable holds an unknown platform name, or
+    /// if the current OS/architecture combination is not supported.
+    pub fn detect_with(options: &DetectOptions) -> crate::Result<DetectedPlatform> {
+        detect_on(&CurrentHost, options)
+    }
+}
+
+fn detect_on(host: &impl Host, options: &DetectOptions) -> crate::Result<DetectedPlatform> {
+    let detected = |platform, source| DetectedPlatform {
+        platform,
+        source,
+        os: host.os(),
+        arch: host.arch(),
+    };
+
+    if let Some(env_var) = options.override_env_var.as_deref()
+        && let Some(value) = host.env_var(env_var).filter(|value| !value.is_empty())
+    {
+        let platform = value
+            .parse::<Platform>()
+            .context(Error::InvalidPlatformOverride {
+                env_var: env_var.to_owned(),
+                value: value.clone(),
+            })?;
+        return Ok(detected(
+            platform,
+            DetectionSource::Override {
+                env_var: env_var.to_owned(),
+            },
+        ));
+    }
+
+    let platform = Platform::detect_for(host.os(), host.arch())?;
+
+    if options.prefer_native {
+        if platform == Platform::MacX64 && host.is_rosetta_translated() {
+            return Ok(detected(Platform::MacArm64, DetectionSource::Rosetta));
+        }
+        // Set by WOW64 for 32-bit processes only, naming the native architecture.
+        if platform == Platform::Win32
+            && host
+                .env_var("PROCESSOR_ARCHITEW6432")
+                .is_some_and(|arch| arch.eq_ignore_ascii_case("AMD64"))
+        {
+            return Ok(detected(Platform::Win64, DetectionSource::Wow64));
+        }
+    }
+
+    Ok(detected(platform, DetectionSource::CompileTarget))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct FakeHost {
+        os: &'static str,
+        arch: &'static str,
+        env: HashMap<&'static str, &'static str>,
+        rosetta: bool,
+    }
+
+    impl Host for FakeHost {
+        fn os(&self) -> &'static str {
+            self.os
+        }
+
+        fn arch(&self) -> &'static str {
+            self.arch
+        }
+
+        fn env_var(&self, name: &str) -> Option<String> {
+            self.env.get(name).map(ToString::to_string)
+        }
+
+        fn is_rosetta_translated(&self) -> bool {
+            self.rosetta
+        }
+    }
+
+    #[test]
+    fn uses_compile_target_by_default() {
+        let host = FakeHost {
+            os: "linux",
+            arch: "x86_64",
+            ..FakeHost::default()
+        };
+
+        let detected = detect_on(&host, &DetectOptions::default()).unwrap();
+
+        assert_that!(detected.to_string())
+            .is_equal_to("linux64 (process compiled for os: linux, arch: x86_64)");
+        assert_that!(detected.platform).is_equal_to(Platform::Linux64);
+        assert_that!(detected.source).is_equal_to(DetectionSource::CompileTarget);
+    }
+
+    #[test]
+    fn prefers_native_arm64_under_rosetta() {
+        let host = FakeHost {
+            os: "macos",
+            arch: "x86_64",
+            rosetta: true,
+            ..FakeHost::default()
+        };
+
+        let detected = detect_on(&host, &DetectOptions::default()).unwrap();
+
+        assert_that!(detected.platform).is_equal_to(Platform::MacArm64);
+        assert_that!(detected.source).is_equal_to(DetectionSource::Rosetta);
+    }
+
+    #[test]
+    fn keeps_process_platform_under_rosetta_when_native_is_not_preferred() {
+        let host = FakeHost {
+            os: "macos",
+            arch: "x86_64",
+            rosetta: true,
+            ..FakeHost::default()
+        };
+        let options = DetectOptions {
+            prefer_native: false,
+            ..DetectOptions::default()
+        };
+
+        let detected = detect_on(&host, &options).unwrap();
+
+        assert_that!(detected.platform).is_equal_to(Platform::MacX64);
+        assert_that!(detected.source).is_equal_to(DetectionSource::CompileTarget);
+    }
+
+    #[test]
+    fn prefers_win64_under_wow64() {
+        let host = FakeHost {
+            os: "windows",
+            arch: "x86",
+            env: HashMap::from([("PROCESSOR_ARCHITEW6432", "AMD64")]),
+            ..FakeHost::default()
+        };
+
+        let detected = detect_on(&host, &DetectOptions::default()).unwrap();
+
+        assert_that!(detected.platform).is_equal_to(Platform::Win64);
+        assert_that!(detected.source).is_equal_to(DetectionSource::Wow64);
+    }
+
+    #[test]
+    fn override_env_var_takes_precedence() {
+        let host = FakeHost {
+            os: "freebsd",
+            arch: "x86_64",
+            env: HashMap::from([(PLATFORM_OVERRIDE_ENV_VAR, "linux64")]),
+            ..FakeHost::default()
+        };
+
+        let detected = detect_on(&host, &DetectOptions::default()).unwrap();
+
+        assert_that!(detected.platform).is_equal_to(Platform::Linux64);
+        assert_that!(detected.source).is_equal_to(DetectionSource::Override {
+            env_var: String::from(PLATFORM_OVERRIDE_ENV_VAR),
+        });
+    }
+
+    #[test]
+    fn invalid_override_is_reported() {
+        let host = FakeHost {
+            os: "linux",
+            arch: "x86_64",
+            env: HashMap::from([(PLATFORM_OVERRIDE_ENV_VAR, "amiga")]),
+            ..FakeHost::default()
+        };
+
+        let err = detect_on(&host, &DetectOptions::default()).unwrap_err();
+
+        let Error::InvalidPlatformOverride { env_var, value } = err.current_context() else {
+            panic!(
+                "expected invalid override error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(env_var.as_str()).is_equal_to(PLATFORM_OVERRIDE_ENV_VAR);
+        assert_that!(value.as_str()).is_equal_to("amiga");
+    }
+
+    #[test]
+    fn unsupported_platform_is_reported() {
+        let host = FakeHost {
+            os: "freebsd",
+            arch: "x86_64",
+            ..FakeHost::default()
+        };
+
+        let err = detect_on(&host, &DetectOptions::default()).unwrap_err();
+
+        assert_that!(err.current_context().to_string())
+            .is_equal_to("Platform (os: freebsd, arch: x86_64) is not supported.");
+    }
+}
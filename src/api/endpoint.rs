@@ -0,0 +1,337 @@
+use crate::api::API_BASE_URL;
+use crate::api::platform::Platform;
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use rootcause::report;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+/// Maximum number of response body bytes kept in [`Error::HttpStatus`] and [`Error::WebDriver`].
+pub(crate) const BODY_SNIPPET_LEN: usize = 512;
+
+/// A backend-agnostic description of an HTTP `GET` request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HttpRequest {
+    /// The absolute URL to request.
+    pub url: Url,
+}
+
+/// A download entry skipped while parsing an endpoint response, because its platform is not
+/// known to this crate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SkippedDownload {
+    /// The path of the entry inside the response, e.g. "`versions[3].downloads.chrome[5]`".
+    pub path: String,
+
+    /// The unrecognized upstream platform name, e.g. "`linux-riscv64`".
+    pub platform: String,
+
+    /// The download URL.
+    pub url: String,
+}
+
+/// A parsed endpoint response, together with the download entries skipped while parsing it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Parsed<T> {
+    /// The parsed response.
+    pub value: T,
+
+    /// Download entries skipped because their platform is not known to this crate.
+    pub skipped: Vec<SkippedDownload>,
+}
+
+/// A Chrome for Testing JSON API endpoint, usable with any HTTP backend.
+///
+/// Build the request with [`Endpoint::request`], perform it with the HTTP client of your choice,
+/// and hand the response status and body to [`Endpoint::parse_response`]. The `fetch` functions
+/// available with the `reqwest` feature are thin adapters over this trait.
+///
+/// ```no_run
+/// use chrome_for_testing::{Endpoint, Error, KnownGoodVersions};
+///
+/// fn http_get(url: &str) -> std::io::Result<(u16, Vec<u8>)> {
+///     // Perform the request using `ureq`, `hyper`, a WASM `fetch`, ...
+///     # unimplemented!()
+/// }
+///
+/// fn main() -> chrome_for_testing::Result<()> {
+///     let request = KnownGoodVersions::request()?;
+///     let (status, body) = http_get(request.url.as_str()).map_err(Error::transport)?;
+///     let parsed = KnownGoodVersions::parse_response(status, &body)?;
+///     println!("Found {} Chrome versions", parsed.value.versions.len());
+///     Ok(())
+/// }
+/// ```
+pub trait Endpoint: DeserializeOwned {
+    /// Path of this endpoint, relative to the API base URL.
+    const PATH: &'static str;
+
+    /// Human-readable endpoint name, used in error context.
+    const NAME: &'static str;
+
+    /// Describes the request for this endpoint on the standard API host.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint URL cannot be constructed.
+    fn request() -> crate::Result<HttpRequest> {
+        Self::request_with_base_url(&API_BASE_URL)
+    }
+
+    /// Describes the request for this endpoint on a custom base URL (useful for testing).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint URL cannot be constructed.
+    fn request_with_base_url(base_url: &Url) -> crate::Result<HttpRequest> {
+        let url = base_url
+            .join(Self::PATH)
+            .context_to::<Error>()
+            .attach_with(|| {
+                format!(
+                    "while joining Chrome for Testing {} endpoint path: {}",
+                    Self::NAME,
+                    Self::PATH
+                )
+            })?;
+        Ok(HttpRequest { url })
+    }
+
+    /// Parses the response of this endpoint.
+    ///
+    /// Parsing is lenient towards platforms this crate does not know yet: download entries for
+    /// unknown platforms are skipped instead of failing the whole response, and returned as
+    /// [`Parsed::skipped`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HttpStatus`] if `status` is not a success (2xx) status code, or
+    /// [`Error::Deserialization`] if the body cannot be deserialized.
+    fn parse_response(status: u16, body: &[u8]) -> crate::Result<Parsed<Self>> {
+        if !(200..300).contains(&status) {
+            return Err(report!(Error::HttpStatus {
+                endpoint: Self::NAME,
+                status,
+                body_snippet: body_snippet(body),
+            }));
+        }
+
+        let deserialization_error = |path: String, source| {
+            report!(Error::Deserialization {
+                endpoint: Self::NAME,
+                path,
+                source,
+            })
+        };
+
+        let mut value: Value = serde_json::from_slice(body)
+            .map_err(|err| deserialization_error(String::from("."), err))?;
+        let mut skipped = Vec::new();
+        skip_unknown_platforms(&mut value, String::new(), &mut skipped);
+
+        let value = serde_path_to_error::deserialize(value)
+            .map_err(|err| deserialization_error(err.path().to_string(), err.into_inner()))?;
+        Ok(Parsed { value, skipped })
+    }
+}
+
+/// The beginning of `body`, lossily decoded as UTF-8, as kept in [`Error::HttpStatus`].
+pub(crate) fn body_snippet(body: &[u8]) -> String {
+    let snippet = &body[..body.len().min(BODY_SNIPPET_LEN)];
+    String::from_utf8_lossy(snippet).trim().to_owned()
+}
+
+/// Removes entries with unknown platforms from all `downloads` lists below `value`.
+fn skip_unknown_platforms(value: &mut Value, path: String, skipped: &mut Vec<SkippedDownload>) {
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if key == "downloads"
+                    && let Value::Object(assets) = value
+                {
+                    for (asset, downloads) in assets.iter_mut() {
+                        if let Value::Array(downloads) = downloads {
+                            let asset_path = format!("{}.{asset}", join(key));
+                            retain_known_platforms(downloads, &asset_path, skipped);
+                        }
+                    }
+                } else {
+                    skip_unknown_platforms(value, join(key), skipped);
+                }
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.iter_mut().enumerate() {
+                skip_unknown_platforms(value, format!("{path}[{index}]"), skipped);
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+}
+
+fn retain_known_platforms(
+    downloads: &mut Vec<Value>,
+    path: &str,
+    skipped: &mut Vec<SkippedDownload>,
+) {
+    let mut index = 0;
+    downloads.retain(|download| {
+        let entry_path = format!("{path}[{index}]");
+        index += 1;
+
+        let field = |name| download.get(name).and_then(Value::as_str);
+        match (field("platform"), field("url")) {
+            (Some(platform), Some(url)) if platform.parse::<Platform>().is_err() => {
+                skipped.push(SkippedDownload {
+                    path: entry_path,
+                    platform: platform.to_owned(),
+                    url: url.to_owned(),
+                });
+                false
+            }
+            // Malformed entries are kept, so that deserialization reports them.
+            _ => true,
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::known_good_versions::KnownGoodVersions;
+    use assertr::prelude::*;
+
+    #[test]
+    fn request_is_root_relative_when_base_url_has_path_prefix() {
+        let base_url: Url = "http://localhost:1234/prefix/".parse().unwrap();
+
+        let request = KnownGoodVersions::request_with_base_url(&base_url).unwrap();
+
+        assert_that!(request.url.as_str()).is_equal_to(
+            "http://localhost:1234/chrome-for-testing/known-good-versions-with-downloads.json",
+        );
+    }
+
+    #[test]
+    fn parse_response_deserializes_successful_response() {
+        let body = include_bytes!(
+            "./../../test-data/known_good_versions_with_downloads_test_response.json"
+        );
+
+        let parsed = KnownGoodVersions::parse_response(200, body).unwrap();
+
+        assert_that!(parsed.value.versions.len()).is_equal_to(2);
+    }
+
+    #[test]
+    fn parse_response_rejects_unsuccessful_status() {
+        let err = KnownGoodVersions::parse_response(503, b"Service Unavailable\n").unwrap_err();
+
+        let Error::HttpStatus {
+            endpoint,
+            status,
+            body_snippet,
+        } = err.current_context()
+        else {
+            panic!(
+                "expected HTTP status error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(*endpoint).is_equal_to("KnownGoodVersions");
+        assert_that!(*status).is_equal_to(503);
+        assert_that!(body_snippet.as_str()).is_equal_to("Service Unavailable");
+        assert_that!(err.current_context().is_transient()).is_true();
+    }
+
+    #[test]
+    fn parse_response_truncates_body_snippet() {
+        let body = "x".repeat(2 * BODY_SNIPPET_LEN);
+
+        let err = KnownGoodVersions::parse_response(500, body.as_bytes()).unwrap_err();
+
+        let Error::HttpStatus { body_snippet, .. } = err.current_context() else {
+            panic!(
+                "expected HTTP status error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(body_snippet.len()).is_equal_to(BODY_SNIPPET_LEN);
+    }
+
+    #[test]
+    fn parse_response_reports_path_of_schema_mismatch() {
+        let body =
+            include_str!("./../../test-data/known_good_versions_with_downloads_test_response.json")
+                .replacen(r#""revision": "1613465""#, r#""revision": 1613465"#, 1);
+
+        let err = KnownGoodVersions::parse_response(200, body.as_bytes()).unwrap_err();
+
+        let Error::Deserialization { endpoint, path, .. } = err.current_context() else {
+            panic!(
+                "expected deserialization error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(*endpoint).is_equal_to("KnownGoodVersions");
+        assert_that!(path.as_str()).is_equal_to("versions[1].revision");
+        assert_that!(err.current_context().is_transient()).is_false();
+    }
+
+    #[test]
+    fn parse_response_skips_unknown_platforms() {
+        let body =
+            include_str!("./../../test-data/known_good_versions_with_downloads_test_response.json")
+                .replace(r#""platform": "win32""#, r#""platform": "linux-riscv64""#);
+
+        let parsed = KnownGoodVersions::parse_response(200, body.as_bytes()).unwrap();
+
+        assert_that!(parsed.value.versions[1].downloads.chrome.len()).is_equal_to(4);
+        assert_that!(parsed.skipped.len()).is_equal_to(4);
+        assert_that!(parsed.skipped[0].clone()).is_equal_to(SkippedDownload {
+            path: String::from("versions[0].downloads.chrome[3]"),
+            platform: String::from("linux-riscv64"),
+            url: String::from(
+                "https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/win32/chrome-win32.zip",
+            ),
+        });
+        assert_that!(
+            parsed
+                .skipped
+                .iter()
+                .any(|it| it.path == "versions[1].downloads.chrome-headless-shell[3]")
+        )
+        .is_true();
+    }
+
+    #[test]
+    fn parse_response_keeps_malformed_download_entries() {
+        let body =
+            include_str!("./../../test-data/known_good_versions_with_downloads_test_response.json")
+                .replacen(r#""platform": "win32""#, r#""platform": 32"#, 1);
+
+        let err = KnownGoodVersions::parse_response(200, body.as_bytes()).unwrap_err();
+
+        let Error::Deserialization { path, .. } = err.current_context() else {
+            panic!(
+                "expected deserialization error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(path.as_str()).is_equal_to("versions[0].downloads.chrome[3].platform");
+    }
+}
@@ -0,0 +1,493 @@
+use crate::api::channel::Channel;
+use crate::api::last_known_good_versions::LastKnownGoodVersions;
+use crate::api::version::Version;
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use rootcause::report;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use time::OffsetDateTime;
+
+/// The versions assigned to each release channel at one point in time, as recorded from a
+/// [`LastKnownGoodVersions`] response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelSnapshot {
+    /// When the Chrome for Testing data was last updated.
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+
+    /// The version of each release channel.
+    pub channels: HashMap<Channel, ChannelVersion>,
+}
+
+/// The version assigned to a release channel in a [`ChannelSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelVersion {
+    /// The version identifier.
+    pub version: Version,
+
+    /// The Chromium revision number.
+    pub revision: String,
+}
+
+/// A change of the version assigned to a release channel between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelPromotion {
+    /// The release channel.
+    pub channel: Channel,
+
+    /// The timestamp of the first snapshot with the new version.
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+
+    /// The previous version, or `None` for the first recorded version.
+    pub from: Option<Version>,
+
+    /// The new version.
+    pub to: Version,
+}
+
+impl From<&LastKnownGoodVersions> for ChannelSnapshot {
+    fn from(versions: &LastKnownGoodVersions) -> Self {
+        ChannelSnapshot {
+            timestamp: versions.timestamp,
+            channels: versions
+                .channels()
+                .iter()
+                .map(|(channel, entry)| {
+                    (
+                        channel.clone(),
+                        ChannelVersion {
+                            version: entry.version,
+                            revision: entry.revision.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A local, append-only history of [`LastKnownGoodVersions`] snapshots, answering which version
+/// a release channel had at a given time.
+///
+/// [`KnownGoodVersions`](crate::KnownGoodVersions) carries no channel information, so recording
+/// snapshots periodically is the only way to learn historical channel assignments, e.g. the
+/// Stable versions to bisect a regression over.
+///
+/// Snapshots are stored as one JSON object per line. Recording only appends to the file, except
+/// for discarding an unterminated last line left behind by an interrupted write.
+///
+/// ```no_run
+/// # #[cfg(feature = "reqwest")]
+/// # async fn example() -> chrome_for_testing::Result<()> {
+/// use chrome_for_testing::{Channel, ChannelHistory, LastKnownGoodVersions};
+///
+/// let mut history = ChannelHistory::open("channel-history.jsonl")?;
+/// let latest = LastKnownGoodVersions::fetch(&reqwest::Client::new()).await?;
+/// history.record(&latest)?;
+///
+/// let at = time::macros::datetime!(2025-06-01 0:00 UTC);
+/// if let Some(stable) = history.version_at(&Channel::Stable, at) {
+///     println!("Stable on {at}: {stable}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChannelHistory {
+    path: PathBuf,
+
+    /// Sorted by timestamp, without duplicate timestamps.
+    snapshots: Vec<ChannelSnapshot>,
+
+    /// Start of an unterminated, invalid last line, truncated on the next record.
+    incomplete_line: Option<u64>,
+}
+
+impl ChannelHistory {
+    /// Opens the history stored at `path`. A missing file is treated as an empty history and
+    /// created on the first [`ChannelHistory::record`].
+    ///
+    /// An unterminated last line which is no valid snapshot is ignored, as it is the remainder of
+    /// an interrupted write. It is removed on the next [`ChannelHistory::record`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or [`Error::CorruptChannelHistory`] if a
+    /// complete line is no valid snapshot.
+    pub fn open(path: impl Into<PathBuf>) -> crate::Result<Self> {
+        let path = path.into();
+        let mut history = ChannelHistory {
+            path,
+            snapshots: Vec::new(),
+            incomplete_line: None,
+        };
+
+        let contents = match std::fs::read_to_string(&history.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(history),
+            Err(err) => {
+                return Err(report!(Error::Io(err))
+                    .attach(format!("while reading '{}'", history.path.display())));
+            }
+        };
+        let mut offset = 0;
+        for (index, line) in contents.split_inclusive('\n').enumerate() {
+            let start = offset;
+            offset += line.len();
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ChannelSnapshot>(line) {
+                Ok(snapshot) => history.insert(snapshot),
+                Err(_) if !line.ends_with('\n') => history.incomplete_line = Some(start as u64),
+                Err(source) => {
+                    return Err(report!(Error::CorruptChannelHistory {
+                        path: history.path.clone(),
+                        line: index + 1,
+                        source,
+                    }));
+                }
+            }
+        }
+
+        Ok(history)
+    }
+
+    /// The file this history is stored in.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All recorded snapshots, oldest first.
+    #[must_use]
+    pub fn snapshots(&self) -> &[ChannelSnapshot] {
+        &self.snapshots
+    }
+
+    /// Appends a snapshot of `versions` to the history, unless a snapshot with the same
+    /// timestamp was already recorded. Returns whether the snapshot was appended.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn record(&mut self, versions: &LastKnownGoodVersions) -> crate::Result<bool> {
+        self.record_snapshot(ChannelSnapshot::from(versions))
+    }
+
+    /// Appends `snapshot` to the history, unless a snapshot with the same timestamp was already
+    /// recorded. Returns whether the snapshot was appended.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn record_snapshot(&mut self, snapshot: ChannelSnapshot) -> crate::Result<bool> {
+        if self.position(snapshot.timestamp).is_ok() {
+            return Ok(false);
+        }
+
+        let line =
+            serde_json::to_string(&snapshot).expect("ChannelSnapshot to be serializable as JSON");
+        self.append_line(&line)
+            .context_to::<Error>()
+            .attach_with(|| format!("while appending to '{}'", self.path.display()))?;
+
+        self.insert(snapshot);
+        Ok(true)
+    }
+
+    /// The version `channel` had at `at`, i.e. its version in the latest snapshot taken at or
+    /// before `at`. `None` if no such snapshot assigns a version to `channel`.
+    #[must_use]
+    pub fn version_at(&self, channel: &Channel, at: OffsetDateTime) -> Option<&Version> {
+        let end = match self.position(at) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        };
+        self.snapshots[..end]
+            .iter()
+            .rev()
+            .find_map(|snapshot| snapshot.channels.get(channel))
+            .map(|entry| &entry.version)
+    }
+
+    /// Every change of the version assigned to `channel`, oldest first. The first entry is the
+    /// first recorded version.
+    #[must_use]
+    pub fn promotions(&self, channel: &Channel) -> Vec<ChannelPromotion> {
+        let mut promotions = Vec::new();
+        let mut current = None;
+        for snapshot in &self.snapshots {
+            let Some(entry) = snapshot.channels.get(channel) else {
+                continue;
+            };
+            if current != Some(entry.version) {
+                promotions.push(ChannelPromotion {
+                    channel: channel.clone(),
+                    timestamp: snapshot.timestamp,
+                    from: current,
+                    to: entry.version,
+                });
+                current = Some(entry.version);
+            }
+        }
+        promotions
+    }
+
+    /// Appends `line`, first dropping an incomplete last line or terminating a complete one.
+    fn append_line(&mut self, line: &str) -> std::io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+        if let Some(start) = self.incomplete_line {
+            file.set_len(start)?;
+            self.incomplete_line = None;
+        }
+
+        let mut contents = String::with_capacity(line.len() + 2);
+        let length = file.metadata()?.len();
+        if length > 0 {
+            let mut last = [0; 1];
+            file.seek(SeekFrom::Start(length - 1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                contents.push('\n');
+            }
+        }
+        contents.push_str(line);
+        contents.push('\n');
+        file.write_all(contents.as_bytes())
+    }
+
+    fn position(&self, timestamp: OffsetDateTime) -> Result<usize, usize> {
+        self.snapshots
+            .binary_search_by_key(&timestamp, |snapshot| snapshot.timestamp)
+    }
+
+    fn insert(&mut self, snapshot: ChannelSnapshot) {
+        match self.position(snapshot.timestamp) {
+            Ok(index) => self.snapshots[index] = snapshot,
+            Err(index) => self.snapshots.insert(index, snapshot),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+    use time::macros::datetime;
+
+    fn last_known_good_versions(
+        timestamp: &str,
+        stable: &str,
+        beta: &str,
+    ) -> LastKnownGoodVersions {
+        let channel = |name: &str, version: &str| {
+            format!(
+                r#""{name}": {{
+                    "channel": "{name}",
+                    "version": "{version}",
+                    "revision": "1",
+                    "downloads": {{ "chrome": [], "chromedriver": [], "chrome-headless-shell": [] }}
+                }}"#
+            )
+        };
+        serde_json::from_str(&format!(
+            r#"{{ "timestamp": "{timestamp}", "channels": {{ {}, {} }} }}"#,
+            channel("Stable", stable),
+            channel("Beta", beta),
+        ))
+        .unwrap()
+    }
+
+    fn version(value: &str) -> Version {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn records_snapshots_and_answers_queries_after_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+
+        let mut history = ChannelHistory::open(&path).unwrap();
+        for (timestamp, stable, beta) in [
+            (
+                "2025-05-20T08:00:00.000Z",
+                "136.0.7103.113",
+                "137.0.7151.27",
+            ),
+            ("2025-05-28T08:00:00.000Z", "137.0.7151.55", "138.0.7204.4"),
+            ("2025-06-03T08:00:00.000Z", "137.0.7151.68", "138.0.7204.15"),
+        ] {
+            let recorded = history
+                .record(&last_known_good_versions(timestamp, stable, beta))
+                .unwrap();
+            assert_that!(recorded).is_true();
+        }
+        let recorded = history
+            .record(&last_known_good_versions(
+                "2025-05-28T08:00:00.000Z",
+                "137.0.7151.55",
+                "138.0.7204.4",
+            ))
+            .unwrap();
+        assert_that!(recorded).is_false();
+
+        let history = ChannelHistory::open(&path).unwrap();
+
+        assert_that!(history.snapshots().len()).is_equal_to(3);
+        assert_that!(history.version_at(&Channel::Stable, datetime!(2025-06-01 0:00 UTC)))
+            .is_equal_to(Some(&version("137.0.7151.55")));
+        assert_that!(history.version_at(&Channel::Beta, datetime!(2025-06-03 8:00 UTC)))
+            .is_equal_to(Some(&version("138.0.7204.15")));
+        assert_that!(history.version_at(&Channel::Stable, datetime!(2025-05-01 0:00 UTC)))
+            .is_none();
+        assert_that!(history.version_at(&Channel::Dev, datetime!(2025-06-01 0:00 UTC))).is_none();
+    }
+
+    #[test]
+    fn lists_promotions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = ChannelHistory::open(dir.path().join("history.jsonl")).unwrap();
+        // Recorded out of order on purpose.
+        for (timestamp, stable) in [
+            ("2025-05-28T08:00:00.000Z", "137.0.7151.55"),
+            ("2025-05-20T08:00:00.000Z", "136.0.7103.113"),
+            ("2025-05-21T08:00:00.000Z", "136.0.7103.113"),
+            ("2025-06-03T08:00:00.000Z", "137.0.7151.68"),
+        ] {
+            history
+                .record(&last_known_good_versions(timestamp, stable, "138.0.7204.4"))
+                .unwrap();
+        }
+
+        let promotions = history.promotions(&Channel::Stable);
+
+        assert_that!(promotions).is_equal_to(vec![
+            ChannelPromotion {
+                channel: Channel::Stable,
+                timestamp: datetime!(2025-05-20 8:00 UTC),
+                from: None,
+                to: version("136.0.7103.113"),
+            },
+            ChannelPromotion {
+                channel: Channel::Stable,
+                timestamp: datetime!(2025-05-28 8:00 UTC),
+                from: Some(version("136.0.7103.113")),
+                to: version("137.0.7151.55"),
+            },
+            ChannelPromotion {
+                channel: Channel::Stable,
+                timestamp: datetime!(2025-06-03 8:00 UTC),
+                from: Some(version("137.0.7151.55")),
+                to: version("137.0.7151.68"),
+            },
+        ]);
+        assert_that!(history.promotions(&Channel::Beta).len()).is_equal_to(1);
+    }
+
+    #[test]
+    fn reports_corrupt_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let mut history = ChannelHistory::open(&path).unwrap();
+        history
+            .record(&last_known_good_versions(
+                "2025-05-20T08:00:00.000Z",
+                "136.0.7103.113",
+                "137.0.7151.27",
+            ))
+            .unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"timestamp\": \"2025-05-\n").unwrap();
+
+        let err = ChannelHistory::open(&path).unwrap_err();
+
+        let Error::CorruptChannelHistory { line, .. } = err.current_context() else {
+            panic!(
+                "expected corrupt channel history error, got: {:?}",
+                err.current_context()
+            );
+        };
+        assert_that!(*line).is_equal_to(2);
+    }
+
+    #[test]
+    fn discards_interrupted_write_on_next_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let mut history = ChannelHistory::open(&path).unwrap();
+        history
+            .record(&last_known_good_versions(
+                "2025-05-20T08:00:00.000Z",
+                "136.0.7103.113",
+                "137.0.7151.27",
+            ))
+            .unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"timestamp\": \"2025-05-").unwrap();
+
+        let mut history = ChannelHistory::open(&path).unwrap();
+        assert_that!(history.snapshots().len()).is_equal_to(1);
+        history
+            .record(&last_known_good_versions(
+                "2025-05-28T08:00:00.000Z",
+                "137.0.7151.55",
+                "138.0.7204.4",
+            ))
+            .unwrap();
+
+        let history = ChannelHistory::open(&path).unwrap();
+        assert_that!(history.snapshots().len()).is_equal_to(2);
+        assert_that!(std::fs::read_to_string(&path).unwrap().lines().count()).is_equal_to(2);
+    }
+
+    #[test]
+    fn terminates_complete_last_line_before_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let snapshot = ChannelSnapshot::from(&last_known_good_versions(
+            "2025-05-20T08:00:00.000Z",
+            "136.0.7103.113",
+            "137.0.7151.27",
+        ));
+        std::fs::write(&path, serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        let mut history = ChannelHistory::open(&path).unwrap();
+        assert_that!(history.snapshots().len()).is_equal_to(1);
+        history
+            .record(&last_known_good_versions(
+                "2025-05-28T08:00:00.000Z",
+                "137.0.7151.55",
+                "138.0.7204.4",
+            ))
+            .unwrap();
+
+        let history = ChannelHistory::open(&path).unwrap();
+        assert_that!(history.snapshots().len()).is_equal_to(2);
+    }
+
+    #[test]
+    fn promotion_serde() {
+        let promotion = ChannelPromotion {
+            channel: Channel::Stable,
+            timestamp: datetime!(2025-05-28 8:00 UTC),
+            from: Some(version("136.0.7103.113")),
+            to: version("137.0.7151.55"),
+        };
+
+        let json = serde_json::to_string(&promotion).unwrap();
+
+        assert_that!(serde_json::from_str::<ChannelPromotion>(&json))
+            .is_ok()
+            .is_equal_to(promotion);
+    }
+}
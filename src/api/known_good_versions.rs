@@ -1,7 +1,20 @@
+use crate::api::binary::Binary;
+use crate::api::endpoint::Endpoint;
+#[cfg(feature = "reqwest")]
+use crate::api::endpoint::Parsed;
+#[cfg(feature = "blocking")]
+use crate::api::fetch_endpoint_blocking;
 use crate::api::platform::Platform;
-use crate::api::version::Version;
-use crate::api::{API_BASE_URL, Download, DownloadsByPlatform, fetch_endpoint};
+use crate::api::version::{PartialVersion, Version, VersionMatch};
+#[cfg(feature = "reqwest")]
+use crate::api::{API_BASE_URL, fetch_endpoint};
+use crate::api::{
+    Download, DownloadsByPlatform, ResolvedDownload, require_download, resolve_with_fallbacks,
+};
+use crate::error::Error;
+use rootcause::report;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// JSON Example:
 /// ```json
@@ -72,6 +85,47 @@ impl Downloads {
             .as_deref()?
             .for_platform(platform)
     }
+
+    /// Returns the download entry of the given binary for the given platform, if available.
+    #[must_use]
+    pub fn binary_for_platform(&self, binary: Binary, platform: Platform) -> Option<&Download> {
+        match binary {
+            Binary::Chrome => self.chrome_for_platform(platform),
+            Binary::ChromeDriver => self.chromedriver_for_platform(platform),
+            Binary::ChromeHeadlessShell => self.chrome_headless_shell_for_platform(platform),
+        }
+    }
+
+    /// Returns the download entry of the given binary for the given platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NoDownloadForPlatform`](crate::Error::NoDownloadForPlatform) if no such
+    /// download is available.
+    pub fn resolve(&self, binary: Binary, platform: Platform) -> crate::Result<&Download> {
+        require_download(self.binary_for_platform(binary, platform), binary, platform)
+    }
+
+    /// Returns the download entry of the given binary for the given platform, falling back to
+    /// the platforms in [`Platform::fallbacks`] if none is available.
+    ///
+    /// Check [`ResolvedDownload::is_fallback`] to find out whether a fallback was chosen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NoDownloadForPlatform`](crate::Error::NoDownloadForPlatform) if neither
+    /// the platform nor any of its fallbacks has a download.
+    pub fn resolve_with_fallbacks(
+        &self,
+        binary: Binary,
+        platform: Platform,
+    ) -> crate::Result<ResolvedDownload<'_>> {
+        resolve_with_fallbacks(
+            |platform| self.binary_for_platform(binary, platform),
+            binary,
+            platform,
+        )
+    }
 }
 
 /// An entry of the "known good versions" API response, representing one version.
@@ -89,6 +143,19 @@ pub struct VersionWithoutChannel {
     pub downloads: Downloads,
 }
 
+/// A `ChromeDriver` download chosen to match a Chrome version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChromeDriverMatch<'a> {
+    /// The version of the chosen `ChromeDriver`.
+    pub version: Version,
+
+    /// The `ChromeDriver` download entry.
+    pub download: &'a Download,
+
+    /// How closely the `ChromeDriver` version matches the Chrome version.
+    pub kind: VersionMatch,
+}
+
 /// Response structure for the "known good versions" API endpoint.
 ///
 /// Contains a comprehensive list of Chrome versions that have been tested and verified to work.
@@ -111,10 +178,14 @@ impl KnownGoodVersions {
     /// Unlike the "last known good versions" API, this includes all historical versions without
     /// channel assignments.
     ///
+    /// Download entries for platforms unknown to this crate are skipped. Use
+    /// [`KnownGoodVersions::fetch_parsed`] to inspect them.
+    ///
     /// # Errors
     ///
     /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
     /// deserialization fails.
+    #[cfg(feature = "reqwest")]
     pub async fn fetch(client: &reqwest::Client) -> crate::Result<Self> {
         Self::fetch_with_base_url(client, &API_BASE_URL).await
     }
@@ -125,130 +196,436 @@ impl KnownGoodVersions {
     ///
     /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
     /// deserialization fails.
+    #[cfg(feature = "reqwest")]
     pub async fn fetch_with_base_url(
         client: &reqwest::Client,
-        base_url: &reqwest::Url,
+        base_url: &url::Url,
     ) -> crate::Result<Self> {
-        fetch_endpoint::<Self>(
-            client,
-            base_url,
-            KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH,
-            "KnownGoodVersions",
-        )
-        .await
+        Ok(fetch_endpoint::<Self>(client, base_url).await?.value)
     }
+
+    /// Like [`KnownGoodVersions::fetch`], but also returns the download entries skipped because their
+    /// platform is not known to this crate, e.g. to warn about them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
+    /// deserialization fails.
+    #[cfg(feature = "reqwest")]
+    pub async fn fetch_parsed(client: &reqwest::Client) -> crate::Result<Parsed<Self>> {
+        fetch_endpoint::<Self>(client, &API_BASE_URL).await
+    }
+
+    /// Blocking variant of [`KnownGoodVersions::fetch`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
+    /// deserialization fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(client: &reqwest::blocking::Client) -> crate::Result<Self> {
+        Self::fetch_with_base_url_blocking(client, &API_BASE_URL)
+    }
+
+    /// Blocking variant of [`KnownGoodVersions::fetch_with_base_url`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
+    /// deserialization fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_with_base_url_blocking(
+        client: &reqwest::blocking::Client,
+        base_url: &url::Url,
+    ) -> crate::Result<Self> {
+        Ok(fetch_endpoint_blocking::<Self>(client, base_url)?.value)
+    }
+
+    /// Blocking variant of [`KnownGoodVersions::fetch_parsed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
+    /// deserialization fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_parsed_blocking(
+        client: &reqwest::blocking::Client,
+    ) -> crate::Result<Parsed<Self>> {
+        fetch_endpoint_blocking::<Self>(client, &API_BASE_URL)
+    }
+
+    /// Finds the `ChromeDriver` download best matching the given Chrome version.
+    ///
+    /// Prefers the exact version, then the newest version of the same build (first three
+    /// components), then the newest version of the same milestone (major version). Only versions
+    /// with a `ChromeDriver` download for `platform` are considered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NoMatchingChromeDriver`] if no version of the same milestone has a
+    /// `ChromeDriver` download for `platform`.
+    pub fn matching_chromedriver(
+        &self,
+        chrome: Version,
+        platform: Platform,
+    ) -> crate::Result<ChromeDriverMatch<'_>> {
+        self.versions
+            .iter()
+            .filter_map(|entry| {
+                let kind = chrome.match_with(&entry.version)?;
+                let download = entry.downloads.chromedriver_for_platform(platform)?;
+                Some(ChromeDriverMatch {
+                    version: entry.version,
+                    download,
+                    kind,
+                })
+            })
+            .max_by_key(|candidate| (candidate.kind.rank(), candidate.version))
+            .ok_or_else(|| report!(Error::NoMatchingChromeDriver { chrome, platform }))
+    }
+
+    /// Finds the newest version matching `version`, e.g. the latest "131" release.
+    #[must_use]
+    pub fn latest_matching(&self, version: &PartialVersion) -> Option<&VersionWithoutChannel> {
+        self.versions
+            .iter()
+            .filter(|entry| version.matches(&entry.version))
+            .max_by_key(|entry| entry.version)
+    }
+
+    /// Determines the version of the Chrome executable at `executable` using
+    /// [`chrome::installed_version`](crate::chrome::installed_version), and finds the
+    /// `ChromeDriver` download best matching it as described in
+    /// [`KnownGoodVersions::matching_chromedriver`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Chrome version cannot be determined, or if no matching
+    /// `ChromeDriver` download is available.
+    pub fn chromedriver_for_installed_chrome(
+        &self,
+        executable: impl AsRef<Path>,
+        platform: Platform,
+    ) -> crate::Result<ChromeDriverMatch<'_>> {
+        let chrome = crate::chrome::installed_version(executable)?;
+        self.matching_chromedriver(chrome, platform)
+    }
+}
+
+impl Endpoint for KnownGoodVersions {
+    const PATH: &'static str = KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH;
+    const NAME: &'static str = "KnownGoodVersions";
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::Download;
     use crate::api::known_good_versions::KnownGoodVersions;
     use crate::api::platform::Platform;
     use crate::api::version::Version;
     use crate::error::Error;
     use assertr::prelude::*;
-    use time::macros::datetime;
-    use url::Url;
-
-    // This test should not be `#[ignore]`, even though it hits the Chrome For Testing API.
-    #[tokio::test]
-    async fn can_request_from_real_world_endpoint() {
-        let result = KnownGoodVersions::fetch(&reqwest::Client::new()).await;
-        assert_that!(result).is_ok();
-    }
-
-    //noinspection DuplicatedCode
-    #[tokio::test]
-    async fn can_query_known_good_versions_api_endpoint_and_deserialize_response() {
-        let mut server = mockito::Server::new_async().await;
-        let _mock = server
-            .mock("GET", KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
-            .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body(include_str!(
-                "./../../test-data/known_good_versions_with_downloads_test_response.json"
-            ))
-            .create();
-
-        let mock_url: Url = server.url().parse().unwrap();
-
-        let data = KnownGoodVersions::fetch_with_base_url(&reqwest::Client::new(), &mock_url)
-            .await
+
+    const FIXTURE_JSON: &str =
+        include_str!("./../../test-data/known_good_versions_with_downloads_test_response.json");
+
+    fn fixture() -> KnownGoodVersions {
+        serde_json::from_str(FIXTURE_JSON).unwrap()
+    }
+
+    #[test]
+    fn resolve_returns_download_for_available_platform() {
+        let data = fixture();
+
+        assert_that!(
+            data.versions[1]
+                .downloads
+                .resolve(Binary::ChromeDriver, Platform::Win64)
+                .map(|it| it.url.clone())
+        )
+        .is_ok()
+        .is_equal_to(String::from(
+            "https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chromedriver-win64.zip",
+        ));
+    }
+
+    #[test]
+    fn resolve_reports_missing_download_for_platform() {
+        let data = fixture();
+
+        let err = data.versions[1]
+            .downloads
+            .resolve(Binary::Chrome, Platform::LinuxArm64)
+            .unwrap_err();
+
+        assert_that!(err.current_context().to_string())
+            .is_equal_to("No chrome download is available for platform 'linux-arm64'.");
+    }
+
+    #[test]
+    fn resolve_with_fallbacks_prefers_requested_platform() {
+        let data = fixture();
+
+        let resolved = data.versions[0]
+            .downloads
+            .resolve_with_fallbacks(Binary::Chrome, Platform::MacArm64)
             .unwrap();
 
-        assert_that!(data).is_equal_to(KnownGoodVersions {
-            timestamp: datetime!(2026-04-13 08:53:52.847 UTC),
-            versions: vec![
-                VersionWithoutChannel {
-                    version: Version { major: 113, minor: 0, patch: 5672, build: 0 },
-                    revision: String::from("1121455"),
-                    downloads: Downloads {
-                        chrome: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/linux64/chrome-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/mac-arm64/chrome-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/mac-x64/chrome-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/win32/chrome-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/win64/chrome-win64.zip") },
-                        ],
-                        chromedriver: None,
-                        chrome_headless_shell: None,
-                    },
-                },
-                VersionWithoutChannel {
-                    version: Version { major: 149, minor: 0, patch: 7789, build: 0 },
-                    revision: String::from("1613465"),
-                    downloads: Downloads {
-                        chrome: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chrome-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chrome-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chrome-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chrome-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chrome-win64.zip") },
-                        ],
-                        chromedriver: Some(vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chromedriver-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chromedriver-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chromedriver-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chromedriver-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chromedriver-win64.zip") },
-                        ]),
-                        chrome_headless_shell: Some(vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chrome-headless-shell-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chrome-headless-shell-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chrome-headless-shell-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chrome-headless-shell-win64.zip") },
-                        ]),
-                    },
-                },
-            ],
-        });
-    }
-
-    #[tokio::test]
-    async fn unsuccessful_http_status_is_reported_as_request_error() {
-        let mut server = mockito::Server::new_async().await;
-        let _mock = server
-            .mock("GET", KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
-            .with_status(500)
-            .with_header("content-type", "application/json")
-            .with_body(include_str!(
-                "./../../test-data/known_good_versions_with_downloads_test_response.json"
-            ))
-            .create();
-
-        let url: Url = server.url().parse().unwrap();
-
-        let err = KnownGoodVersions::fetch_with_base_url(&reqwest::Client::new(), &url)
-            .await
+        assert_that!(resolved.platform()).is_equal_to(Platform::MacArm64);
+        assert_that!(resolved.is_fallback()).is_false();
+    }
+
+    #[test]
+    fn resolve_with_fallbacks_walks_fallback_chain() {
+        let data: KnownGoodVersions = serde_json::from_str(
+            &FIXTURE_JSON.replace(r#""platform": "mac-arm64""#, r#""platform": "linux-arm64""#),
+        )
+        .unwrap();
+
+        let resolved = data.versions[0]
+            .downloads
+            .resolve_with_fallbacks(Binary::Chrome, Platform::MacArm64)
+            .unwrap();
+
+        assert_that!(resolved.requested).is_equal_to(Platform::MacArm64);
+        assert_that!(resolved.platform()).is_equal_to(Platform::MacX64);
+        assert_that!(resolved.is_fallback()).is_true();
+        assert_that!(resolved.download.url.as_str()).is_equal_to(
+            "https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/mac-x64/chrome-mac-x64.zip",
+        );
+    }
+
+    #[test]
+    fn resolve_with_fallbacks_reports_requested_platform_when_chain_is_exhausted() {
+        let data = fixture();
+
+        let err = data.versions[0]
+            .downloads
+            .resolve_with_fallbacks(Binary::ChromeDriver, Platform::Win64)
+            .unwrap_err();
+
+        let Error::NoDownloadForPlatform { binary, platform } = err.current_context() else {
+            panic!(
+                "expected missing download error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(*binary).is_equal_to(Binary::ChromeDriver);
+        assert_that!(*platform).is_equal_to(Platform::Win64);
+    }
+
+    #[test]
+    fn matching_chromedriver_prefers_exact_then_build_then_milestone() {
+        let data = fixture();
+        let driver_version: Version = "149.0.7789.0".parse().unwrap();
+
+        let cases = [
+            ("149.0.7789.0", VersionMatch::Exact),
+            ("149.0.7789.12", VersionMatch::SameBuild),
+            ("149.0.7790.3", VersionMatch::SameMilestone),
+        ];
+        for (chrome, expected) in cases {
+            let matched = data
+                .matching_chromedriver(chrome.parse().unwrap(), Platform::Linux64)
+                .unwrap();
+
+            assert_that!(matched.kind).is_equal_to(expected);
+            assert_that!(matched.version).is_equal_to(driver_version);
+            assert_that!(matched.download.url.as_str()).is_equal_to(
+                "https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chromedriver-linux64.zip",
+            );
+        }
+    }
+
+    #[test]
+    fn latest_matching_finds_newest_matching_version() {
+        let data = fixture();
+
+        let cases = [
+            ("149", Some("149.0.7789.0")),
+            ("113.0", Some("113.0.5672.0")),
+            ("149.0.7790", None),
+        ];
+        for (partial, expected) in cases {
+            let found = data
+                .latest_matching(&partial.parse().unwrap())
+                .map(|entry| entry.version.to_string());
+
+            assert_that!(found).is_equal_to(expected.map(String::from));
+        }
+    }
+
+    #[test]
+    fn matching_chromedriver_ignores_versions_without_chromedriver_download() {
+        let data = fixture();
+
+        let err = data
+            .matching_chromedriver("113.0.5672.0".parse().unwrap(), Platform::Linux64)
             .unwrap_err();
 
-        let Error::Request(request_error) = err.current_context() else {
-            panic!("expected request error, got: {:?}", err.current_context());
+        let Error::NoMatchingChromeDriver { chrome, platform } = err.current_context() else {
+            panic!(
+                "expected missing ChromeDriver error, got: {:?}",
+                err.current_context()
+            );
         };
 
-        assert_that!(request_error.status())
-            .is_equal_to(Some(reqwest::StatusCode::INTERNAL_SERVER_ERROR));
+        assert_that!(chrome.to_string()).is_equal_to("113.0.5672.0");
+        assert_that!(*platform).is_equal_to(Platform::Linux64);
+    }
+
+    #[test]
+    fn resolve_reports_missing_chromedriver_downloads_of_old_versions() {
+        let data = fixture();
+
+        let err = data.versions[0]
+            .downloads
+            .resolve(Binary::ChromeDriver, Platform::Linux64)
+            .unwrap_err();
+
+        let Error::NoDownloadForPlatform { binary, platform } = err.current_context() else {
+            panic!(
+                "expected missing download error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(*binary).is_equal_to(Binary::ChromeDriver);
+        assert_that!(*platform).is_equal_to(Platform::Linux64);
+    }
+
+    #[cfg(feature = "reqwest")]
+    mod http {
+        use super::*;
+        use crate::api::Download;
+        use time::macros::datetime;
+        use url::Url;
+
+        // This test should not be `#[ignore]`, even though it hits the Chrome For Testing API.
+        #[tokio::test]
+        async fn can_request_from_real_world_endpoint() {
+            let result = KnownGoodVersions::fetch(&reqwest::Client::new()).await;
+            assert_that!(result).is_ok();
+        }
+
+        //noinspection DuplicatedCode
+        #[tokio::test]
+        async fn can_query_known_good_versions_api_endpoint_and_deserialize_response() {
+            let mut server = mockito::Server::new_async().await;
+            let _mock = server
+                .mock("GET", KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(FIXTURE_JSON)
+                .create();
+
+            let mock_url: Url = server.url().parse().unwrap();
+
+            let data = KnownGoodVersions::fetch_with_base_url(&reqwest::Client::new(), &mock_url)
+                .await
+                .unwrap();
+
+            assert_that!(data).is_equal_to(KnownGoodVersions {
+                timestamp: datetime!(2026-04-13 08:53:52.847 UTC),
+                versions: vec![
+                    VersionWithoutChannel {
+                        version: Version { major: 113, minor: 0, patch: 5672, build: 0 },
+                        revision: String::from("1121455"),
+                        downloads: Downloads {
+                            chrome: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/linux64/chrome-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/mac-arm64/chrome-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/mac-x64/chrome-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/win32/chrome-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/113.0.5672.0/win64/chrome-win64.zip") },
+                            ],
+                            chromedriver: None,
+                            chrome_headless_shell: None,
+                        },
+                    },
+                    VersionWithoutChannel {
+                        version: Version { major: 149, minor: 0, patch: 7789, build: 0 },
+                        revision: String::from("1613465"),
+                        downloads: Downloads {
+                            chrome: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chrome-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chrome-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chrome-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chrome-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chrome-win64.zip") },
+                            ],
+                            chromedriver: Some(vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chromedriver-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chromedriver-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chromedriver-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chromedriver-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chromedriver-win64.zip") },
+                            ]),
+                            chrome_headless_shell: Some(vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chrome-headless-shell-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chrome-headless-shell-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chrome-headless-shell-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chrome-headless-shell-win64.zip") },
+                            ]),
+                        },
+                    },
+                ],
+            });
+        }
+
+        #[cfg(feature = "blocking")]
+        #[test]
+        fn can_query_known_good_versions_api_endpoint_blocking() {
+            let mut server = mockito::Server::new();
+            let _mock = server
+                .mock("GET", KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(FIXTURE_JSON)
+                .create();
+
+            let url: Url = server.url().parse().unwrap();
+
+            let data = KnownGoodVersions::fetch_with_base_url_blocking(
+                &reqwest::blocking::Client::new(),
+                &url,
+            )
+            .unwrap();
+
+            assert_that!(data.timestamp).is_equal_to(datetime!(2026-04-13 08:53:52.847 UTC));
+            assert_that!(data.versions.len()).is_equal_to(2);
+        }
+
+        #[tokio::test]
+        async fn unsuccessful_http_status_is_reported_as_http_status_error() {
+            let mut server = mockito::Server::new_async().await;
+            let _mock = server
+                .mock("GET", KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
+                .with_status(500)
+                .with_header("content-type", "application/json")
+                .with_body(FIXTURE_JSON)
+                .create();
+
+            let url: Url = server.url().parse().unwrap();
+
+            let err = KnownGoodVersions::fetch_with_base_url(&reqwest::Client::new(), &url)
+                .await
+                .unwrap_err();
+
+            let Error::HttpStatus {
+                endpoint, status, ..
+            } = err.current_context()
+            else {
+                panic!(
+                    "expected HTTP status error, got: {:?}",
+                    err.current_context()
+                );
+            };
+
+            assert_that!(*endpoint).is_equal_to("KnownGoodVersions");
+            assert_that!(*status).is_equal_to(500);
+        }
     }
 }
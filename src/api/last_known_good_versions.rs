@@ -1,7 +1,17 @@
+use crate::api::binary::Binary;
 use crate::api::channel::Channel;
+use crate::api::endpoint::Endpoint;
+#[cfg(feature = "reqwest")]
+use crate::api::endpoint::Parsed;
+#[cfg(feature = "blocking")]
+use crate::api::fetch_endpoint_blocking;
 use crate::api::platform::Platform;
 use crate::api::version::Version;
-use crate::api::{API_BASE_URL, Download, DownloadsByPlatform, fetch_endpoint};
+#[cfg(feature = "reqwest")]
+use crate::api::{API_BASE_URL, fetch_endpoint};
+use crate::api::{
+    Download, DownloadsByPlatform, ResolvedDownload, require_download, resolve_with_fallbacks,
+};
 use serde::de::Error as DeError;
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
@@ -115,6 +125,47 @@ impl Downloads {
     pub fn chrome_headless_shell_for_platform(&self, platform: Platform) -> Option<&Download> {
         self.chrome_headless_shell.for_platform(platform)
     }
+
+    /// Returns the download entry of the given binary for the given platform, if available.
+    #[must_use]
+    pub fn binary_for_platform(&self, binary: Binary, platform: Platform) -> Option<&Download> {
+        match binary {
+            Binary::Chrome => self.chrome_for_platform(platform),
+            Binary::ChromeDriver => self.chromedriver_for_platform(platform),
+            Binary::ChromeHeadlessShell => self.chrome_headless_shell_for_platform(platform),
+        }
+    }
+
+    /// Returns the download entry of the given binary for the given platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NoDownloadForPlatform`](crate::Error::NoDownloadForPlatform) if no such
+    /// download is available.
+    pub fn resolve(&self, binary: Binary, platform: Platform) -> crate::Result<&Download> {
+        require_download(self.binary_for_platform(binary, platform), binary, platform)
+    }
+
+    /// Returns the download entry of the given binary for the given platform, falling back to
+    /// the platforms in [`Platform::fallbacks`] if none is available.
+    ///
+    /// Check [`ResolvedDownload::is_fallback`] to find out whether a fallback was chosen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NoDownloadForPlatform`](crate::Error::NoDownloadForPlatform) if neither
+    /// the platform nor any of its fallbacks has a download.
+    pub fn resolve_with_fallbacks(
+        &self,
+        binary: Binary,
+        platform: Platform,
+    ) -> crate::Result<ResolvedDownload<'_>> {
+        resolve_with_fallbacks(
+            |platform| self.binary_for_platform(binary, platform),
+            binary,
+            platform,
+        )
+    }
 }
 
 /// A Chrome version entry with channel information.
@@ -176,10 +227,14 @@ impl LastKnownGoodVersions {
     ///
     /// Returns the most recent version for each Chrome release channel (Stable, Beta, Dev, Canary).
     ///
+    /// Download entries for platforms unknown to this crate are skipped. Use
+    /// [`LastKnownGoodVersions::fetch_parsed`] to inspect them.
+    ///
     /// # Errors
     ///
     /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
     /// deserialization fails.
+    #[cfg(feature = "reqwest")]
     pub async fn fetch(client: &reqwest::Client) -> crate::Result<Self> {
         Self::fetch_with_base_url(client, &API_BASE_URL).await
     }
@@ -190,17 +245,62 @@ impl LastKnownGoodVersions {
     ///
     /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
     /// deserialization fails.
+    #[cfg(feature = "reqwest")]
     pub async fn fetch_with_base_url(
         client: &reqwest::Client,
-        base_url: &reqwest::Url,
+        base_url: &url::Url,
     ) -> crate::Result<LastKnownGoodVersions> {
-        fetch_endpoint::<Self>(
-            client,
-            base_url,
-            LAST_KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH,
-            "LastKnownGoodVersions",
-        )
-        .await
+        Ok(fetch_endpoint::<Self>(client, base_url).await?.value)
+    }
+
+    /// Like [`LastKnownGoodVersions::fetch`], but also returns the download entries skipped because their
+    /// platform is not known to this crate, e.g. to warn about them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
+    /// deserialization fails.
+    #[cfg(feature = "reqwest")]
+    pub async fn fetch_parsed(client: &reqwest::Client) -> crate::Result<Parsed<Self>> {
+        fetch_endpoint::<Self>(client, &API_BASE_URL).await
+    }
+
+    /// Blocking variant of [`LastKnownGoodVersions::fetch`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
+    /// deserialization fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_blocking(client: &reqwest::blocking::Client) -> crate::Result<Self> {
+        Self::fetch_with_base_url_blocking(client, &API_BASE_URL)
+    }
+
+    /// Blocking variant of [`LastKnownGoodVersions::fetch_with_base_url`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
+    /// deserialization fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_with_base_url_blocking(
+        client: &reqwest::blocking::Client,
+        base_url: &url::Url,
+    ) -> crate::Result<Self> {
+        Ok(fetch_endpoint_blocking::<Self>(client, base_url)?.value)
+    }
+
+    /// Blocking variant of [`LastKnownGoodVersions::fetch_parsed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails, the response has an unsuccessful status, or
+    /// deserialization fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_parsed_blocking(
+        client: &reqwest::blocking::Client,
+    ) -> crate::Result<Parsed<Self>> {
+        fetch_endpoint_blocking::<Self>(client, &API_BASE_URL)
     }
 
     /// Returns the version info for the given channel.
@@ -240,196 +340,16 @@ impl LastKnownGoodVersions {
     }
 }
 
+impl Endpoint for LastKnownGoodVersions {
+    const PATH: &'static str = LAST_KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH;
+    const NAME: &'static str = "LastKnownGoodVersions";
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::api::Download;
     use crate::api::channel::Channel;
-    use crate::api::last_known_good_versions::{
-        Downloads, LAST_KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH, LastKnownGoodVersions,
-        VersionInChannel,
-    };
-    use crate::api::platform::Platform;
-    use crate::api::version::Version;
-    use crate::error::Error;
+    use crate::api::last_known_good_versions::LastKnownGoodVersions;
     use assertr::prelude::*;
-    use std::collections::HashMap;
-    use time::macros::datetime;
-    use url::Url;
-
-    // This test should not be `#[ignore]`, even though it hits the Chrome For Testing API.
-    #[tokio::test]
-    async fn can_request_from_real_world_endpoint() {
-        let result = LastKnownGoodVersions::fetch(&reqwest::Client::new()).await;
-        assert_that!(result).is_ok();
-    }
-
-    //noinspection DuplicatedCode
-    #[tokio::test]
-    #[allow(clippy::too_many_lines)]
-    async fn can_query_last_known_good_versions_api_endpoint_and_deserialize_response() {
-        let mut server = mockito::Server::new_async().await;
-        let _mock = server
-            .mock("GET", LAST_KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
-            .with_status(200)
-            .with_header("content-type", "application/json")
-            .with_body(include_str!(
-                "./../../test-data/last_known_good_versions_with_downloads_test_response.json"
-            ))
-            .create();
-
-        let url: Url = server.url().parse().unwrap();
-
-        let data = LastKnownGoodVersions::fetch_with_base_url(&reqwest::Client::new(), &url)
-            .await
-            .unwrap();
-
-        assert_that!(data).is_equal_to(LastKnownGoodVersions {
-            timestamp: datetime!(2026-04-13 08:53:52.841 UTC),
-            channels: HashMap::from([
-                (
-                    Channel::Stable,
-                    VersionInChannel {
-                    channel: Channel::Stable,
-                    version: Version { major: 147, minor: 0, patch: 7727, build: 56 },
-                    revision: String::from("1596535"),
-                    downloads: Downloads {
-                        chrome: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/linux64/chrome-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-arm64/chrome-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-x64/chrome-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win32/chrome-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win64/chrome-win64.zip") },
-                        ],
-                        chromedriver: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/linux64/chromedriver-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-arm64/chromedriver-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-x64/chromedriver-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win32/chromedriver-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win64/chromedriver-win64.zip") },
-                        ],
-                        chrome_headless_shell: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/linux64/chrome-headless-shell-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-x64/chrome-headless-shell-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win32/chrome-headless-shell-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win64/chrome-headless-shell-win64.zip") },
-                        ],
-                    },
-                    }
-                ),
-                (Channel::Beta, VersionInChannel {
-                    channel: Channel::Beta,
-                    version: Version { major: 148, minor: 0, patch: 7778, build: 5 },
-                    revision: String::from("1610480"),
-                    downloads: Downloads {
-                        chrome: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/linux64/chrome-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-arm64/chrome-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-x64/chrome-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win32/chrome-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win64/chrome-win64.zip") },
-                        ],
-                        chromedriver: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/linux64/chromedriver-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-arm64/chromedriver-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-x64/chromedriver-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win32/chromedriver-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win64/chromedriver-win64.zip") },
-                        ],
-                        chrome_headless_shell: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/linux64/chrome-headless-shell-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-x64/chrome-headless-shell-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win32/chrome-headless-shell-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win64/chrome-headless-shell-win64.zip") },
-                        ],
-                    },
-                }),
-                (Channel::Dev, VersionInChannel {
-                    channel: Channel::Dev,
-                    version: Version { major: 148, minor: 0, patch: 7766, build: 3 },
-                    revision: String::from("1607787"),
-                    downloads: Downloads {
-                        chrome: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/linux64/chrome-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-arm64/chrome-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-x64/chrome-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win32/chrome-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win64/chrome-win64.zip") },
-                        ],
-                        chromedriver: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/linux64/chromedriver-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-arm64/chromedriver-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-x64/chromedriver-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win32/chromedriver-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win64/chromedriver-win64.zip") },
-                        ],
-                        chrome_headless_shell: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/linux64/chrome-headless-shell-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-x64/chrome-headless-shell-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win32/chrome-headless-shell-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win64/chrome-headless-shell-win64.zip") },
-                        ],
-                    },
-                }),
-                (Channel::Canary, VersionInChannel {
-                    channel: Channel::Canary,
-                    version: Version { major: 149, minor: 0, patch: 7789, build: 0 },
-                    revision: String::from("1613465"),
-                    downloads: Downloads {
-                        chrome: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chrome-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chrome-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chrome-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chrome-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chrome-win64.zip") },
-                        ],
-                        chromedriver: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chromedriver-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chromedriver-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chromedriver-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chromedriver-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chromedriver-win64.zip") },
-                        ],
-                        chrome_headless_shell: vec![
-                            Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chrome-headless-shell-linux64.zip") },
-                            Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
-                            Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chrome-headless-shell-mac-x64.zip") },
-                            Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chrome-headless-shell-win32.zip") },
-                            Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chrome-headless-shell-win64.zip") },
-                        ],
-                    },
-                }),
-            ]),
-        });
-    }
-
-    #[tokio::test]
-    async fn unsuccessful_http_status_is_reported_as_request_error() {
-        let mut server = mockito::Server::new_async().await;
-        let _mock = server
-            .mock("GET", LAST_KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
-            .with_status(500)
-            .with_header("content-type", "application/json")
-            .with_body(include_str!(
-                "./../../test-data/last_known_good_versions_with_downloads_test_response.json"
-            ))
-            .create();
-
-        let url: Url = server.url().parse().unwrap();
-
-        let err = LastKnownGoodVersions::fetch_with_base_url(&reqwest::Client::new(), &url)
-            .await
-            .unwrap_err();
-
-        let Error::Request(request_error) = err.current_context() else {
-            panic!("expected request error, got: {:?}", err.current_context());
-        };
-
-        assert_that!(request_error.status())
-            .is_equal_to(Some(reqwest::StatusCode::INTERNAL_SERVER_ERROR));
-    }
 
     #[test]
     fn deserialization_rejects_channel_mismatch() {
@@ -463,4 +383,200 @@ mod tests {
             .derive(|it| it.channel.clone())
             .is_equal_to(extended);
     }
+
+    #[cfg(feature = "reqwest")]
+    mod http {
+        use super::*;
+        use crate::api::Download;
+        use crate::api::last_known_good_versions::{
+            Downloads, LAST_KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH, VersionInChannel,
+        };
+        use crate::api::platform::Platform;
+        use crate::api::version::Version;
+        use crate::error::Error;
+        use std::collections::HashMap;
+        use time::macros::datetime;
+        use url::Url;
+
+        // This test should not be `#[ignore]`, even though it hits the Chrome For Testing API.
+        #[tokio::test]
+        async fn can_request_from_real_world_endpoint() {
+            let result = LastKnownGoodVersions::fetch(&reqwest::Client::new()).await;
+            assert_that!(result).is_ok();
+        }
+
+        //noinspection DuplicatedCode
+        #[tokio::test]
+        #[allow(clippy::too_many_lines)]
+        async fn can_query_last_known_good_versions_api_endpoint_and_deserialize_response() {
+            let mut server = mockito::Server::new_async().await;
+            let _mock = server
+                .mock("GET", LAST_KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(include_str!(
+                    "./../../test-data/last_known_good_versions_with_downloads_test_response.json"
+                ))
+                .create();
+
+            let url: Url = server.url().parse().unwrap();
+
+            let data = LastKnownGoodVersions::fetch_with_base_url(&reqwest::Client::new(), &url)
+                .await
+                .unwrap();
+
+            assert_that!(data).is_equal_to(LastKnownGoodVersions {
+                timestamp: datetime!(2026-04-13 08:53:52.841 UTC),
+                channels: HashMap::from([
+                    (
+                        Channel::Stable,
+                        VersionInChannel {
+                        channel: Channel::Stable,
+                        version: Version { major: 147, minor: 0, patch: 7727, build: 56 },
+                        revision: String::from("1596535"),
+                        downloads: Downloads {
+                            chrome: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/linux64/chrome-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-arm64/chrome-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-x64/chrome-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win32/chrome-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win64/chrome-win64.zip") },
+                            ],
+                            chromedriver: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/linux64/chromedriver-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-arm64/chromedriver-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-x64/chromedriver-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win32/chromedriver-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win64/chromedriver-win64.zip") },
+                            ],
+                            chrome_headless_shell: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/linux64/chrome-headless-shell-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/mac-x64/chrome-headless-shell-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win32/chrome-headless-shell-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/147.0.7727.56/win64/chrome-headless-shell-win64.zip") },
+                            ],
+                        },
+                        }
+                    ),
+                    (Channel::Beta, VersionInChannel {
+                        channel: Channel::Beta,
+                        version: Version { major: 148, minor: 0, patch: 7778, build: 5 },
+                        revision: String::from("1610480"),
+                        downloads: Downloads {
+                            chrome: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/linux64/chrome-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-arm64/chrome-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-x64/chrome-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win32/chrome-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win64/chrome-win64.zip") },
+                            ],
+                            chromedriver: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/linux64/chromedriver-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-arm64/chromedriver-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-x64/chromedriver-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win32/chromedriver-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win64/chromedriver-win64.zip") },
+                            ],
+                            chrome_headless_shell: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/linux64/chrome-headless-shell-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/mac-x64/chrome-headless-shell-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win32/chrome-headless-shell-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7778.5/win64/chrome-headless-shell-win64.zip") },
+                            ],
+                        },
+                    }),
+                    (Channel::Dev, VersionInChannel {
+                        channel: Channel::Dev,
+                        version: Version { major: 148, minor: 0, patch: 7766, build: 3 },
+                        revision: String::from("1607787"),
+                        downloads: Downloads {
+                            chrome: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/linux64/chrome-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-arm64/chrome-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-x64/chrome-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win32/chrome-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win64/chrome-win64.zip") },
+                            ],
+                            chromedriver: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/linux64/chromedriver-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-arm64/chromedriver-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-x64/chromedriver-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win32/chromedriver-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win64/chromedriver-win64.zip") },
+                            ],
+                            chrome_headless_shell: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/linux64/chrome-headless-shell-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/mac-x64/chrome-headless-shell-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win32/chrome-headless-shell-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/148.0.7766.3/win64/chrome-headless-shell-win64.zip") },
+                            ],
+                        },
+                    }),
+                    (Channel::Canary, VersionInChannel {
+                        channel: Channel::Canary,
+                        version: Version { major: 149, minor: 0, patch: 7789, build: 0 },
+                        revision: String::from("1613465"),
+                        downloads: Downloads {
+                            chrome: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chrome-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chrome-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chrome-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chrome-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chrome-win64.zip") },
+                            ],
+                            chromedriver: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chromedriver-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chromedriver-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chromedriver-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chromedriver-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chromedriver-win64.zip") },
+                            ],
+                            chrome_headless_shell: vec![
+                                Download { platform: Platform::Linux64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/linux64/chrome-headless-shell-linux64.zip") },
+                                Download { platform: Platform::MacArm64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-arm64/chrome-headless-shell-mac-arm64.zip") },
+                                Download { platform: Platform::MacX64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/mac-x64/chrome-headless-shell-mac-x64.zip") },
+                                Download { platform: Platform::Win32, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win32/chrome-headless-shell-win32.zip") },
+                                Download { platform: Platform::Win64, url: String::from("https://storage.googleapis.com/chrome-for-testing-public/149.0.7789.0/win64/chrome-headless-shell-win64.zip") },
+                            ],
+                        },
+                    }),
+                ]),
+            });
+        }
+
+        #[tokio::test]
+        async fn unsuccessful_http_status_is_reported_as_http_status_error() {
+            let mut server = mockito::Server::new_async().await;
+            let _mock = server
+                .mock("GET", LAST_KNOWN_GOOD_VERSIONS_WITH_DOWNLOADS_JSON_PATH)
+                .with_status(500)
+                .with_header("content-type", "application/json")
+                .with_body(include_str!(
+                    "./../../test-data/last_known_good_versions_with_downloads_test_response.json"
+                ))
+                .create();
+
+            let url: Url = server.url().parse().unwrap();
+
+            let err = LastKnownGoodVersions::fetch_with_base_url(&reqwest::Client::new(), &url)
+                .await
+                .unwrap_err();
+
+            let Error::HttpStatus {
+                endpoint, status, ..
+            } = err.current_context()
+            else {
+                panic!(
+                    "expected HTTP status error, got: {:?}",
+                    err.current_context()
+                );
+            };
+
+            assert_that!(*endpoint).is_equal_to("LastKnownGoodVersions");
+            assert_that!(*status).is_equal_to(500);
+        }
+    }
 }
@@ -1,27 +1,53 @@
+use crate::api::binary::Binary;
+#[cfg(feature = "reqwest")]
+use crate::api::endpoint::{Endpoint, Parsed, body_snippet};
 use crate::api::version::Version;
 use crate::error::Error;
+#[cfg(feature = "reqwest")]
+use crate::progress::{Progress, ProgressPhase, ProgressReporter};
 use platform::Platform;
-use reqwest::Url;
 use rootcause::prelude::ResultExt;
-use serde::de::DeserializeOwned;
+use rootcause::report;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "reqwest")]
+use std::fs::File;
+#[cfg(feature = "reqwest")]
+use std::io::{BufReader, BufWriter, Seek, Write};
+#[cfg(feature = "reqwest")]
+use std::path::Path;
 use std::sync::LazyLock;
+use url::Url;
 
 /// Chrome release channel definitions.
 pub mod channel;
 
+/// The binaries distributed by Chrome for Testing.
+pub mod binary;
+
 /// Platform identification for different operating systems and architectures.
 pub mod platform;
 
+/// Platform detection considering emulation layers and overrides.
+pub mod detection;
+
 /// Version parsing and representation.
 pub mod version;
 
+/// Unpacking of downloaded archives.
+pub mod archive;
+
+/// HTTP-backend-agnostic request construction and response parsing.
+pub mod endpoint;
+
 /// API request for a list of working releases. None are assigned to any channel.
 pub mod known_good_versions;
 
 /// The last working releases for each channel.
 pub mod last_known_good_versions;
 
+/// A local history of channel assignments, recorded from last-known-good-versions snapshots.
+pub mod history;
+
 /// The standard chrome-for-testing API endpoint protocol and hostname.
 ///
 /// Consult <https://github.com/GoogleChromeLabs/chrome-for-testing#json-api-endpoints>
@@ -53,6 +79,218 @@ impl Download {
             )
         })
     }
+
+    /// Downloads the archive behind this entry, streaming its bytes into `writer`.
+    ///
+    /// Reports [`ProgressPhase::Downloading`] updates to `progress` once before the first chunk
+    /// and after every received chunk. Writes to `writer` are blocking, so prefer a buffered
+    /// writer. Returns the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is invalid, the HTTP request fails, the response has an
+    /// unsuccessful status ([`Error::HttpStatus`]), or writing to `writer` fails.
+    #[cfg(feature = "reqwest")]
+    pub async fn fetch_to_writer(
+        &self,
+        client: &reqwest::Client,
+        writer: &mut impl Write,
+        progress: &impl ProgressReporter,
+    ) -> crate::Result<u64> {
+        let mut response = client
+            .get(self.parsed_url()?)
+            .send()
+            .await
+            .context_to::<Error>()
+            .attach_with(|| format!("while requesting download: '{}'", self.url))?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.bytes().await.unwrap_or_default();
+            return Err(self.status_error(status, &body));
+        }
+
+        let mut sink = DownloadSink::new(writer, progress, response.content_length());
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .context_to::<Error>()
+            .attach_with(|| format!("while receiving download: '{}'", self.url))?
+        {
+            sink.write(&chunk)?;
+        }
+        sink.finish()
+    }
+
+    /// Blocking variant of [`Download::fetch_to_writer`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is invalid, the HTTP request fails, the response has an
+    /// unsuccessful status ([`Error::HttpStatus`]), or writing to `writer` fails.
+    #[cfg(feature = "blocking")]
+    pub fn fetch_to_writer_blocking(
+        &self,
+        client: &reqwest::blocking::Client,
+        writer: &mut impl Write,
+        progress: &impl ProgressReporter,
+    ) -> crate::Result<u64> {
+        use std::io::Read;
+
+        let mut response = client
+            .get(self.parsed_url()?)
+            .send()
+            .context_to::<Error>()
+            .attach_with(|| format!("while requesting download: '{}'", self.url))?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.bytes().unwrap_or_default();
+            return Err(self.status_error(status, &body));
+        }
+
+        let mut sink = DownloadSink::new(writer, progress, response.content_length());
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            let read = response
+                .read(&mut buffer)
+                .context_to::<Error>()
+                .attach_with(|| format!("while receiving download: '{}'", self.url))?;
+            if read == 0 {
+                break;
+            }
+            sink.write(&buffer[..read])?;
+        }
+        sink.finish()
+    }
+
+    /// Downloads the archive behind this entry and unpacks it into `dir`.
+    ///
+    /// The archive is buffered in an anonymous temporary file. Reports
+    /// [`ProgressPhase::Downloading`] updates first, then [`ProgressPhase::Extracting`] updates
+    /// (see [`extract_archive`](crate::extract_archive)). Unpacking uses blocking file system
+    /// calls. Returns the number of unpacked bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if downloading fails, the archive is invalid, or writing to `dir` fails.
+    #[cfg(feature = "reqwest")]
+    pub async fn install(
+        &self,
+        client: &reqwest::Client,
+        dir: impl AsRef<Path>,
+        progress: &impl ProgressReporter,
+    ) -> crate::Result<u64> {
+        let mut archive = archive_file()?;
+        self.fetch_to_writer(client, &mut archive, progress).await?;
+        self.unpack(archive, dir.as_ref(), progress)
+    }
+
+    /// Blocking variant of [`Download::install`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if downloading fails, the archive is invalid, or writing to `dir` fails.
+    #[cfg(feature = "blocking")]
+    pub fn install_blocking(
+        &self,
+        client: &reqwest::blocking::Client,
+        dir: impl AsRef<Path>,
+        progress: &impl ProgressReporter,
+    ) -> crate::Result<u64> {
+        let mut archive = archive_file()?;
+        self.fetch_to_writer_blocking(client, &mut archive, progress)?;
+        self.unpack(archive, dir.as_ref(), progress)
+    }
+
+    /// Reports an unsuccessful download response the way [`Endpoint::parse_response`] reports
+    /// endpoint responses.
+    #[cfg(feature = "reqwest")]
+    fn status_error(&self, status: u16, body: &[u8]) -> rootcause::Report<Error> {
+        report!(Error::HttpStatus {
+            endpoint: "Download",
+            status,
+            body_snippet: body_snippet(body),
+        })
+        .attach(format!("while requesting download: '{}'", self.url))
+    }
+
+    #[cfg(feature = "reqwest")]
+    fn unpack(
+        &self,
+        archive: BufWriter<File>,
+        dir: &Path,
+        progress: &impl ProgressReporter,
+    ) -> crate::Result<u64> {
+        let mut archive = archive
+            .into_inner()
+            .map_err(std::io::IntoInnerError::into_error)
+            .context_to::<Error>()
+            .attach("while flushing downloaded archive")?;
+        archive
+            .rewind()
+            .context_to::<Error>()
+            .attach("while rewinding downloaded archive")?;
+        archive::extract_archive(BufReader::new(archive), dir, progress)
+            .attach_with(|| format!("while installing download: '{}'", self.url))
+    }
+}
+
+/// Creates the anonymous temporary file downloads are buffered in before unpacking.
+#[cfg(feature = "reqwest")]
+fn archive_file() -> crate::Result<BufWriter<File>> {
+    tempfile::tempfile()
+        .map(BufWriter::new)
+        .context_to::<Error>()
+        .attach("while creating temporary archive file")
+}
+
+/// Writes downloaded chunks and reports progress, shared by the async and blocking download
+/// paths.
+#[cfg(feature = "reqwest")]
+struct DownloadSink<'a, W, P> {
+    writer: &'a mut W,
+    progress: &'a P,
+    bytes_processed: u64,
+    total_bytes: Option<u64>,
+}
+
+#[cfg(feature = "reqwest")]
+impl<'a, W: Write, P: ProgressReporter> DownloadSink<'a, W, P> {
+    fn new(writer: &'a mut W, progress: &'a P, total_bytes: Option<u64>) -> Self {
+        let sink = Self {
+            writer,
+            progress,
+            bytes_processed: 0,
+            total_bytes,
+        };
+        sink.report();
+        sink
+    }
+
+    fn write(&mut self, chunk: &[u8]) -> crate::Result<()> {
+        self.writer
+            .write_all(chunk)
+            .context_to::<Error>()
+            .attach("while writing downloaded bytes")?;
+        self.bytes_processed += chunk.len() as u64;
+        self.report();
+        Ok(())
+    }
+
+    fn finish(self) -> crate::Result<u64> {
+        self.writer
+            .flush()
+            .context_to::<Error>()
+            .attach("while flushing downloaded bytes")?;
+        Ok(self.bytes_processed)
+    }
+
+    fn report(&self) {
+        self.progress.report(Progress {
+            phase: ProgressPhase::Downloading,
+            bytes_processed: self.bytes_processed,
+            total_bytes: self.total_bytes,
+        });
+    }
 }
 
 /// Extension trait for download slices, providing platform-based lookup.
@@ -67,6 +305,60 @@ impl DownloadsByPlatform for [Download] {
     }
 }
 
+/// Turns a missing download into an [`Error::NoDownloadForPlatform`] report.
+fn require_download(
+    download: Option<&Download>,
+    binary: Binary,
+    platform: Platform,
+) -> crate::Result<&Download> {
+    download.ok_or_else(|| report!(Error::NoDownloadForPlatform { binary, platform }))
+}
+
+/// A download resolved by walking the [`Platform::fallbacks`] chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResolvedDownload<'a> {
+    /// The resolved download entry. Its platform is the one actually chosen.
+    pub download: &'a Download,
+
+    /// The platform originally asked for.
+    pub requested: Platform,
+}
+
+impl ResolvedDownload<'_> {
+    /// The platform of the resolved download.
+    #[must_use]
+    pub fn platform(&self) -> Platform {
+        self.download.platform
+    }
+
+    /// Tells whether a fallback platform had to be used.
+    #[must_use]
+    pub fn is_fallback(&self) -> bool {
+        self.download.platform != self.requested
+    }
+}
+
+/// Looks up a download for `platform`, then for each of its fallbacks.
+fn resolve_with_fallbacks<'a>(
+    lookup: impl Fn(Platform) -> Option<&'a Download>,
+    binary: Binary,
+    platform: Platform,
+) -> crate::Result<ResolvedDownload<'a>> {
+    std::iter::once(platform)
+        .chain(platform.fallbacks().iter().copied())
+        .find_map(&lookup)
+        .map(|download| ResolvedDownload {
+            download,
+            requested: platform,
+        })
+        .ok_or_else(|| {
+            report!(Error::NoDownloadForPlatform { binary, platform }).attach(format!(
+                "also tried fallback platforms: {:?}",
+                platform.fallbacks()
+            ))
+        })
+}
+
 /// Trait for types that contain a version identifier.
 pub trait HasVersion {
     /// Returns the version identifier.
@@ -85,40 +377,61 @@ impl HasVersion for last_known_good_versions::VersionInChannel {
     }
 }
 
-pub(crate) async fn fetch_endpoint<T>(
+#[cfg(feature = "reqwest")]
+pub(crate) async fn fetch_endpoint<E>(
     client: &reqwest::Client,
     base_url: &Url,
-    path: &str,
-    endpoint_name: &str,
-) -> crate::Result<T>
+) -> crate::Result<Parsed<E>>
 where
-    T: DeserializeOwned,
+    E: Endpoint,
 {
-    let url = base_url.join(path).context_to::<Error>().attach_with(|| {
-        format!("while joining Chrome for Testing {endpoint_name} endpoint path: {path}")
-    })?;
+    let request = E::request_with_base_url(base_url)?;
 
-    let result = client
-        .get(url)
+    let response = client
+        .get(request.url)
         .send()
         .await
         .context_to::<Error>()
-        .attach_with(|| format!("while sending Chrome for Testing {endpoint_name} request"))?
-        .error_for_status()
-        .context_to::<Error>()?
-        .json::<T>()
+        .attach_with(|| format!("while sending Chrome for Testing {} request", E::NAME))?;
+    let status = response.status().as_u16();
+    let body = response
+        .bytes()
         .await
         .context_to::<Error>()
-        .attach_with(|| {
-            format!("while deserializing Chrome for Testing {endpoint_name} response")
-        })?;
+        .attach_with(|| format!("while receiving Chrome for Testing {} response", E::NAME))?;
+
+    E::parse_response(status, &body)
+}
+
+#[cfg(feature = "blocking")]
+pub(crate) fn fetch_endpoint_blocking<E>(
+    client: &reqwest::blocking::Client,
+    base_url: &Url,
+) -> crate::Result<Parsed<E>>
+where
+    E: Endpoint,
+{
+    let request = E::request_with_base_url(base_url)?;
+
+    let response = client
+        .get(request.url)
+        .send()
+        .context_to::<Error>()
+        .attach_with(|| format!("while sending Chrome for Testing {} request", E::NAME))?;
+    let status = response.status().as_u16();
+    let body = response
+        .bytes()
+        .context_to::<Error>()
+        .attach_with(|| format!("while receiving Chrome for Testing {} response", E::NAME))?;
 
-    Ok(result)
+    E::parse_response(status, &body)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "reqwest")]
+    use crate::api::known_good_versions::KnownGoodVersions;
     use assertr::prelude::*;
 
     #[test]
@@ -149,29 +462,274 @@ mod tests {
         assert_that!(url_error.to_string()).contains("relative URL without a base");
     }
 
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn fetch_to_writer_streams_body_and_reports_progress() {
+        let mut server = mockito::Server::new_async().await;
+        let body = vec![7u8; 64 * 1024];
+
+        let _mock = server
+            .mock("GET", "/chrome-linux64.zip")
+            .with_status(200)
+            .with_body(&body)
+            .create();
+
+        let download = Download {
+            platform: Platform::Linux64,
+            url: format!("{}/chrome-linux64.zip", server.url()),
+        };
+        let updates = std::cell::RefCell::new(Vec::new());
+        let mut written = Vec::new();
+
+        let bytes = download
+            .fetch_to_writer(&reqwest::Client::new(), &mut written, &|progress| {
+                updates.borrow_mut().push(progress);
+            })
+            .await
+            .unwrap();
+
+        let updates = updates.into_inner();
+        assert_that!(bytes).is_equal_to(body.len() as u64);
+        assert_that!(written).is_equal_to(body.clone());
+        assert_that!(updates.first().copied()).is_equal_to(Some(Progress {
+            phase: ProgressPhase::Downloading,
+            bytes_processed: 0,
+            total_bytes: Some(body.len() as u64),
+        }));
+        assert_that!(updates.last().copied()).is_equal_to(Some(Progress {
+            phase: ProgressPhase::Downloading,
+            bytes_processed: body.len() as u64,
+            total_bytes: Some(body.len() as u64),
+        }));
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn fetch_to_writer_rejects_unsuccessful_status() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/missing.zip")
+            .with_status(404)
+            .with_body("Not Found")
+            .create();
+
+        let download = Download {
+            platform: Platform::Linux64,
+            url: format!("{}/missing.zip", server.url()),
+        };
+
+        let err = download
+            .fetch_to_writer(
+                &reqwest::Client::new(),
+                &mut Vec::new(),
+                &crate::progress::NoProgress,
+            )
+            .await
+            .unwrap_err();
+
+        let Error::HttpStatus {
+            endpoint,
+            status,
+            body_snippet,
+        } = err.current_context()
+        else {
+            panic!(
+                "expected HTTP status error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(*endpoint).is_equal_to("Download");
+        assert_that!(*status).is_equal_to(404);
+        assert_that!(body_snippet.as_str()).is_equal_to("Not Found");
+        assert_that!(err.current_context().is_transient()).is_false();
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn install_downloads_and_extracts_archive() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/chrome-linux64.zip")
+            .with_status(200)
+            .with_body(archive::tests::chrome_archive())
+            .create();
+
+        let download = Download {
+            platform: Platform::Linux64,
+            url: format!("{}/chrome-linux64.zip", server.url()),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let phases = std::cell::RefCell::new(Vec::new());
+
+        download
+            .install(
+                &reqwest::Client::new(),
+                dir.path(),
+                &|progress: Progress| {
+                    let mut phases = phases.borrow_mut();
+                    if phases.last() != Some(&progress.phase) {
+                        phases.push(progress.phase);
+                    }
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_that!(
+            dir.path()
+                .join(Binary::Chrome.executable_path(Platform::Linux64))
+                .is_file()
+        )
+        .is_true();
+        assert_that!(phases.into_inner())
+            .is_equal_to(vec![ProgressPhase::Downloading, ProgressPhase::Extracting]);
+    }
+
+    #[cfg(feature = "reqwest")]
     #[tokio::test]
     async fn fetch_endpoint_path_is_root_relative_when_base_url_has_path_prefix() {
         let mut server = mockito::Server::new_async().await;
-        let endpoint_path = "/chrome-for-testing/test-endpoint.json";
 
         let _mock = server
-            .mock("GET", endpoint_path)
+            .mock("GET", KnownGoodVersions::PATH)
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"ok":true}"#)
+            .with_body(include_str!(
+                "./../../test-data/known_good_versions_with_downloads_test_response.json"
+            ))
             .create();
 
         let url: Url = format!("{}/prefix/", server.url()).parse().unwrap();
 
-        let data = fetch_endpoint::<serde_json::Value>(
-            &reqwest::Client::new(),
-            &url,
-            endpoint_path,
-            "TestEndpoint",
-        )
-        .await
-        .unwrap();
+        let data = fetch_endpoint::<KnownGoodVersions>(&reqwest::Client::new(), &url)
+            .await
+            .unwrap()
+            .value;
+
+        assert_that!(data.versions.len()).is_equal_to(2);
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn fetch_endpoint_returns_skipped_downloads() {
+        let mut server = mockito::Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", KnownGoodVersions::PATH)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "timestamp": "2025-10-11T08:09:10.123Z",
+                    "versions": [{
+                        "version": "142.0.7444.0",
+                        "revision": "1509001",
+                        "downloads": {
+                            "chrome": [
+                                {"platform": "linux64", "url": "https://example.com/chrome-linux64.zip"},
+                                {"platform": "linux-riscv64", "url": "https://example.com/chrome-linux-riscv64.zip"}
+                            ]
+                        }
+                    }]
+                }"#,
+            )
+            .create();
+
+        let url: Url = server.url().parse().unwrap();
+
+        let parsed = fetch_endpoint::<KnownGoodVersions>(&reqwest::Client::new(), &url)
+            .await
+            .unwrap();
+
+        assert_that!(parsed.value.versions[0].downloads.chrome.len()).is_equal_to(1);
+        assert_that!(parsed.skipped.len()).is_equal_to(1);
+        assert_that!(parsed.skipped[0].path.as_str())
+            .is_equal_to("versions[0].downloads.chrome[1]");
+        assert_that!(parsed.skipped[0].platform.as_str()).is_equal_to("linux-riscv64");
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn fetch_endpoint_reports_timeouts() {
+        // Accepts connections into the backlog, but never responds.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url: Url = format!("http://{}", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(100))
+            .build()
+            .unwrap();
+
+        let err = fetch_endpoint::<KnownGoodVersions>(&client, &url)
+            .await
+            .unwrap_err();
+
+        let Error::Timeout(_) = err.current_context() else {
+            panic!("expected timeout error, got: {:?}", err.current_context());
+        };
+
+        assert_that!(err.current_context().is_transient()).is_true();
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn fetch_endpoint_blocking_path_is_root_relative_when_base_url_has_path_prefix() {
+        let mut server = mockito::Server::new();
+
+        let _mock = server
+            .mock("GET", KnownGoodVersions::PATH)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!(
+                "./../../test-data/known_good_versions_with_downloads_test_response.json"
+            ))
+            .create();
+
+        let url: Url = format!("{}/prefix/", server.url()).parse().unwrap();
+
+        let data =
+            fetch_endpoint_blocking::<KnownGoodVersions>(&reqwest::blocking::Client::new(), &url)
+                .unwrap()
+                .value;
+
+        assert_that!(data.versions.len()).is_equal_to(2);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn fetch_to_writer_blocking_streams_body_and_reports_progress() {
+        let mut server = mockito::Server::new();
+        let body = vec![7u8; 64 * 1024 + 1];
+
+        let _mock = server
+            .mock("GET", "/chrome-linux64.zip")
+            .with_status(200)
+            .with_body(&body)
+            .create();
+
+        let download = Download {
+            platform: Platform::Linux64,
+            url: format!("{}/chrome-linux64.zip", server.url()),
+        };
+        let updates = std::cell::RefCell::new(Vec::new());
+        let mut written = Vec::new();
+
+        let bytes = download
+            .fetch_to_writer_blocking(
+                &reqwest::blocking::Client::new(),
+                &mut written,
+                &|progress: Progress| updates.borrow_mut().push(progress.bytes_processed),
+            )
+            .unwrap();
 
-        assert_that!(data["ok"].as_bool()).is_equal_to(Some(true));
+        let updates = updates.into_inner();
+        assert_that!(bytes).is_equal_to(body.len() as u64);
+        assert_that!(written).is_equal_to(body.clone());
+        assert_that!(updates.first().copied()).is_equal_to(Some(0));
+        assert_that!(updates.last().copied()).is_equal_to(Some(body.len() as u64));
     }
 }
@@ -9,7 +9,9 @@ use std::str::FromStr;
 
 /// Error returned when parsing a platform string fails.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
-#[error("Unknown platform: '{value}'. Expected one of: linux64, mac-arm64, mac-x64, win32, win64")]
+#[error(
+    "Unknown platform: '{value}'. Expected one of: linux64, linux-arm64, mac-arm64, mac-x64, win32, win64"
+)]
 pub struct ParsePlatformError {
     value: String,
 }
@@ -17,13 +19,25 @@ pub struct ParsePlatformError {
 /// Supported platforms for Chrome and `ChromeDriver` downloads.
 ///
 /// This site <https://googlechromelabs.github.io/chrome-for-testing/> show the platform names
-/// defined here.
+/// defined here. More platforms may be added as Chrome for Testing starts publishing them.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum Platform {
     /// Linux x64 platform.
     #[serde(rename = "linux64")]
     Linux64,
 
+    /// Linux ARM64 platform.
+    ///
+    /// Chrome for Testing does not publish downloads for this platform yet. It is modeled so that
+    /// platform detection succeeds on ARM64 Linux hosts and download lookups report a missing
+    /// download instead of failing.
+    ///
+    /// The identifier "linux-arm64" is provisional: it follows the naming of the other platforms,
+    /// but may change once upstream publishes this platform.
+    #[serde(rename = "linux-arm64")]
+    LinuxArm64,
+
     /// macOS ARM64 platform (Apple Silicon).
     #[serde(rename = "mac-arm64")]
     MacArm64,
@@ -45,6 +59,7 @@ impl Display for Platform {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
             Platform::Linux64 => "linux64",
+            Platform::LinuxArm64 => "linux-arm64",
             Platform::MacArm64 => "mac-arm64",
             Platform::MacX64 => "mac-x64",
             Platform::Win32 => "win32",
@@ -59,6 +74,7 @@ impl FromStr for Platform {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "linux64" => Ok(Platform::Linux64),
+            "linux-arm64" => Ok(Platform::LinuxArm64),
             "mac-arm64" => Ok(Platform::MacArm64),
             "mac-x64" => Ok(Platform::MacX64),
             "win32" => Ok(Platform::Win32),
@@ -73,38 +89,45 @@ impl FromStr for Platform {
 impl Platform {
     /// Detect the platform identifier that should be used for the current system.
     ///
+    /// This only considers the target this process was compiled for. Use
+    /// [`Platform::detect_with`] to also take emulation layers like Rosetta 2 into account.
+    ///
     /// # Errors
     ///
     /// Returns an error if the current OS/architecture combination is not supported.
     pub fn detect() -> crate::Result<Platform> {
-        match consts::OS {
-            os @ "windows" => match consts::ARCH {
-                "x86" => Ok(Platform::Win32),
-                "x86_64" => Ok(Platform::Win64),
-                arch => Err(report!(Error::UnsupportedPlatform {
-                    os: Cow::Borrowed(os),
-                    arch: Cow::Borrowed(arch),
-                })),
-            },
-            os @ "linux" => match consts::ARCH {
-                "x86_64" => Ok(Platform::Linux64),
-                arch => Err(report!(Error::UnsupportedPlatform {
-                    os: Cow::Borrowed(os),
-                    arch: Cow::Borrowed(arch),
-                })),
-            },
-            os @ "macos" => match consts::ARCH {
-                "x86_64" => Ok(Platform::MacX64),
-                "arm" | "aarch64" => Ok(Platform::MacArm64),
-                arch => Err(report!(Error::UnsupportedPlatform {
-                    os: Cow::Borrowed(os),
-                    arch: Cow::Borrowed(arch),
-                })),
-            },
-            os => Err(report!(Error::UnsupportedPlatform {
+        Self::detect_for(consts::OS, consts::ARCH)
+    }
+
+    /// Maps an OS/architecture pair, as named by [`std::env::consts`], to a platform.
+    pub(crate) fn detect_for(os: &'static str, arch: &'static str) -> crate::Result<Platform> {
+        let unsupported = || {
+            report!(Error::UnsupportedPlatform {
                 os: Cow::Borrowed(os),
-                arch: Cow::Borrowed(consts::ARCH),
-            })),
+                arch: Cow::Borrowed(arch),
+            })
+        };
+        match (os, arch) {
+            ("windows", "x86") => Ok(Platform::Win32),
+            ("windows", "x86_64") => Ok(Platform::Win64),
+            ("linux", "x86_64") => Ok(Platform::Linux64),
+            ("linux", "aarch64") => Ok(Platform::LinuxArm64),
+            ("macos", "x86_64") => Ok(Platform::MacX64),
+            ("macos", "arm" | "aarch64") => Ok(Platform::MacArm64),
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Platforms whose builds can run on this platform under emulation, in order of preference.
+    ///
+    /// Used when no download is available for this platform itself, e.g. for old versions which
+    /// predate `mac-arm64` builds and can still run under Rosetta 2.
+    #[must_use]
+    pub fn fallbacks(self) -> &'static [Platform] {
+        match self {
+            Platform::MacArm64 => &[Platform::MacX64],
+            Platform::Win64 => &[Platform::Win32],
+            Platform::Linux64 | Platform::LinuxArm64 | Platform::MacX64 | Platform::Win32 => &[],
         }
     }
 
@@ -112,7 +135,7 @@ impl Platform {
     #[must_use]
     pub fn chrome_executable_name(self) -> &'static str {
         match self {
-            Platform::Linux64 => "chrome",
+            Platform::Linux64 | Platform::LinuxArm64 => "chrome",
             Platform::MacArm64 | Platform::MacX64 => "Google Chrome for Testing",
             Platform::Win32 | Platform::Win64 => "chrome.exe",
         }
@@ -123,6 +146,7 @@ impl Platform {
     pub fn chrome_executable_path(self) -> &'static Path {
         match self {
             Platform::Linux64 => Path::new("chrome-linux64/chrome"),
+            Platform::LinuxArm64 => Path::new("chrome-linux-arm64/chrome"),
             Platform::MacArm64 => Path::new(
                 "chrome-mac-arm64/Google Chrome for Testing.app/Contents/MacOS/Google Chrome for Testing",
             ),
@@ -138,7 +162,9 @@ impl Platform {
     #[must_use]
     pub fn chromedriver_executable_name(self) -> &'static str {
         match self {
-            Platform::Linux64 | Platform::MacX64 | Platform::MacArm64 => "chromedriver",
+            Platform::Linux64 | Platform::LinuxArm64 | Platform::MacX64 | Platform::MacArm64 => {
+                "chromedriver"
+            }
             Platform::Win32 | Platform::Win64 => "chromedriver.exe",
         }
     }
@@ -148,6 +174,7 @@ impl Platform {
     pub fn chromedriver_executable_path(self) -> &'static Path {
         match self {
             Platform::Linux64 => Path::new("chromedriver-linux64/chromedriver"),
+            Platform::LinuxArm64 => Path::new("chromedriver-linux-arm64/chromedriver"),
             Platform::MacArm64 => Path::new("chromedriver-mac-arm64/chromedriver"),
             Platform::MacX64 => Path::new("chromedriver-mac-x64/chromedriver"),
             Platform::Win32 => Path::new("chromedriver-win32/chromedriver.exe"),
@@ -159,7 +186,9 @@ impl Platform {
     #[must_use]
     pub fn chrome_headless_shell_executable_name(self) -> &'static str {
         match self {
-            Platform::Linux64 | Platform::MacX64 | Platform::MacArm64 => "chrome-headless-shell",
+            Platform::Linux64 | Platform::LinuxArm64 | Platform::MacX64 | Platform::MacArm64 => {
+                "chrome-headless-shell"
+            }
             Platform::Win32 | Platform::Win64 => "chrome-headless-shell.exe",
         }
     }
@@ -170,6 +199,9 @@ impl Platform {
     pub fn chrome_headless_shell_executable_path(self) -> &'static Path {
         match self {
             Platform::Linux64 => Path::new("chrome-headless-shell-linux64/chrome-headless-shell"),
+            Platform::LinuxArm64 => {
+                Path::new("chrome-headless-shell-linux-arm64/chrome-headless-shell")
+            }
             Platform::MacArm64 => {
                 Path::new("chrome-headless-shell-mac-arm64/chrome-headless-shell")
             }
@@ -183,7 +215,7 @@ impl Platform {
     #[must_use]
     pub fn is_linux(&self) -> bool {
         match self {
-            Platform::Linux64 => true,
+            Platform::Linux64 | Platform::LinuxArm64 => true,
             Platform::MacArm64 | Platform::MacX64 | Platform::Win32 | Platform::Win64 => false,
         }
     }
@@ -193,7 +225,7 @@ impl Platform {
     pub fn is_macos(&self) -> bool {
         match self {
             Platform::MacArm64 | Platform::MacX64 => true,
-            Platform::Linux64 | Platform::Win32 | Platform::Win64 => false,
+            Platform::Linux64 | Platform::LinuxArm64 | Platform::Win32 | Platform::Win64 => false,
         }
     }
 
@@ -202,7 +234,9 @@ impl Platform {
     pub fn is_windows(&self) -> bool {
         match self {
             Platform::Win32 | Platform::Win64 => true,
-            Platform::Linux64 | Platform::MacArm64 | Platform::MacX64 => false,
+            Platform::Linux64 | Platform::LinuxArm64 | Platform::MacArm64 | Platform::MacX64 => {
+                false
+            }
         }
     }
 }
@@ -216,6 +250,7 @@ mod tests {
     fn parse_to_string_round_trip() {
         let platforms = [
             ("linux64", Platform::Linux64),
+            ("linux-arm64", Platform::LinuxArm64),
             ("mac-arm64", Platform::MacArm64),
             ("mac-x64", Platform::MacX64),
             ("win32", Platform::Win32),
@@ -239,6 +274,7 @@ mod tests {
     fn executable_path_file_names_match_executable_names() {
         let platforms = [
             Platform::Linux64,
+            Platform::LinuxArm64,
             Platform::MacArm64,
             Platform::MacX64,
             Platform::Win32,
@@ -270,6 +306,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fallbacks_stay_on_the_same_os() {
+        assert_that!(Platform::MacArm64.fallbacks()).is_equal_to([Platform::MacX64].as_slice());
+        assert_that!(Platform::Win64.fallbacks()).is_equal_to([Platform::Win32].as_slice());
+        assert_that!(Platform::Linux64.fallbacks()).is_empty();
+    }
+
     #[test]
     fn serialized_value_matches_display_output() {
         assert_that!(serde_json::to_string(&Platform::Linux64).unwrap())
@@ -53,36 +53,584 @@ impl PartialOrd for Version {
     }
 }
 
+/// How closely two versions match, as used when pairing `ChromeDriver` with Chrome.
+///
+/// Chrome versions have the form `MAJOR.MINOR.BUILD.PATCH`. A `ChromeDriver` of the same
+/// milestone (`MAJOR`) is generally compatible; one of the same build (`MAJOR.MINOR.BUILD`) is the
+/// closest match short of an exact one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VersionMatch {
+    /// All four components are equal.
+    Exact,
+
+    /// The first three components are equal.
+    SameBuild,
+
+    /// Only the first component is equal.
+    SameMilestone,
+}
+
+impl VersionMatch {
+    /// Higher is better.
+    pub(crate) fn rank(self) -> u8 {
+        match self {
+            VersionMatch::Exact => 2,
+            VersionMatch::SameBuild => 1,
+            VersionMatch::SameMilestone => 0,
+        }
+    }
+}
+
+impl Version {
+    /// Creates a version from its four components.
+    #[must_use]
+    pub const fn new(major: u32, minor: u32, patch: u32, build: u32) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+
+    /// The milestone of this version, i.e. its major version.
+    #[must_use]
+    pub const fn milestone(&self) -> Milestone {
+        Milestone(self.major)
+    }
+
+    /// The milestone following the one of this version, or `None` if the major version is
+    /// `u32::MAX`.
+    #[must_use]
+    pub const fn next_milestone(&self) -> Option<Milestone> {
+        self.milestone().next()
+    }
+
+    /// The first three components of this version, e.g. "120.0.6099" for "120.0.6099.109", as
+    /// used by the `LATEST_RELEASE_{BUILD}` scheme.
+    #[must_use]
+    pub fn build_prefix(&self) -> String {
+        format!("{}.{}.{}", self.major, self.minor, self.patch)
+    }
+
+    /// Whether `other` has the same milestone (major version) as this version.
+    #[must_use]
+    pub const fn same_milestone(&self, other: &Version) -> bool {
+        self.major == other.major
+    }
+
+    /// Whether `other` has the same first three components as this version.
+    #[must_use]
+    pub const fn same_build(&self, other: &Version) -> bool {
+        self.major == other.major && self.minor == other.minor && self.patch == other.patch
+    }
+
+    /// Tells how closely `other` matches this version, or `None` if not even the major versions
+    /// are equal.
+    #[must_use]
+    pub fn match_with(&self, other: &Version) -> Option<VersionMatch> {
+        if self == other {
+            Some(VersionMatch::Exact)
+        } else if self.same_build(other) {
+            Some(VersionMatch::SameBuild)
+        } else if self.same_milestone(other) {
+            Some(VersionMatch::SameMilestone)
+        } else {
+            None
+        }
+    }
+}
+
+/// A Chrome milestone, i.e. the major version shared by all releases branched from it.
+///
+/// Serialized as a string, like the `milestone` fields of the Chrome for Testing API, but also
+/// deserialized from an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Milestone(pub u32);
+
+impl Milestone {
+    /// The milestone following this one, or `None` if this milestone is `u32::MAX`.
+    #[must_use]
+    pub const fn next(self) -> Option<Milestone> {
+        match self.0.checked_add(1) {
+            Some(next) => Some(Milestone(next)),
+            None => None,
+        }
+    }
+
+    /// Whether `version` belongs to this milestone.
+    #[must_use]
+    pub const fn contains(self, version: &Version) -> bool {
+        self.0 == version.major
+    }
+}
+
+impl From<u32> for Milestone {
+    fn from(major: u32) -> Self {
+        Milestone(major)
+    }
+}
+
+impl From<Milestone> for u32 {
+    fn from(milestone: Milestone) -> Self {
+        milestone.0
+    }
+}
+
+impl Display for Milestone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Milestone {
+    type Err = Report<ParseVersionError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<u32>().map(Milestone).map_err(|err| {
+            report!(ParseVersionError {
+                message: format!("Failed to parse milestone '{s}' as an u32: {err}"),
+            })
+        })
+    }
+}
+
+impl Serialize for Milestone {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Milestone {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MilestoneVisitor;
+
+        impl Visitor<'_> for MilestoneVisitor {
+            type Value = Milestone;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a milestone as a string or integer, like `\"120\"` or `120`")
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Milestone, E>
+            where
+                E: de::Error,
+            {
+                u32::try_from(value)
+                    .map(Milestone)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Milestone, E>
+            where
+                E: de::Error,
+            {
+                value
+                    .parse::<u32>()
+                    .map(Milestone)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(MilestoneVisitor)
+    }
+}
+
+/// A version with one to four components, like "131", "131.0", "131.0.6778" or
+/// "131.0.6778.204", as written in configuration files.
+///
+/// Missing components match any value. Only trailing components can be missing, so a version
+/// like "131.?.6778" cannot be represented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PartialVersion {
+    /// The specified components, followed by zeros.
+    components: [u32; 4],
+
+    /// The number of specified components, from 1 to 4.
+    len: usize,
+}
+
+impl PartialVersion {
+    /// Creates a partial version from its leading components, e.g. `&[131, 0]` for "131.0".
+    ///
+    /// Returns `None` if `components` is empty or has more than four elements.
+    #[must_use]
+    pub const fn from_components(components: &[u32]) -> Option<Self> {
+        if components.is_empty() || components.len() > 4 {
+            return None;
+        }
+        let mut padded = [0; 4];
+        let mut index = 0;
+        while index < components.len() {
+            padded[index] = components[index];
+            index += 1;
+        }
+        Some(PartialVersion {
+            components: padded,
+            len: components.len(),
+        })
+    }
+
+    /// The specified components, from one to four.
+    #[must_use]
+    pub fn components(&self) -> &[u32] {
+        &self.components[..self.len]
+    }
+
+    /// The major version number.
+    #[must_use]
+    pub const fn major(&self) -> u32 {
+        self.components[0]
+    }
+
+    /// The minor version number, if specified.
+    #[must_use]
+    pub const fn minor(&self) -> Option<u32> {
+        self.component(1)
+    }
+
+    /// The patch version number, if specified.
+    #[must_use]
+    pub const fn patch(&self) -> Option<u32> {
+        self.component(2)
+    }
+
+    /// The build version number, if specified.
+    #[must_use]
+    pub const fn build(&self) -> Option<u32> {
+        self.component(3)
+    }
+
+    const fn component(&self, index: usize) -> Option<u32> {
+        if index < self.len {
+            Some(self.components[index])
+        } else {
+            None
+        }
+    }
+
+    /// Whether `version` starts with the components of this partial version.
+    #[must_use]
+    pub fn matches(&self, version: &Version) -> bool {
+        let version = [version.major, version.minor, version.patch, version.build];
+        self.components() == &version[..self.len]
+    }
+
+    /// The milestone of this partial version, i.e. its major version.
+    #[must_use]
+    pub const fn milestone(&self) -> Milestone {
+        Milestone(self.major())
+    }
+
+    /// Whether all four components are specified.
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.len == 4
+    }
+
+    /// The full version, if all four components are specified.
+    #[must_use]
+    pub const fn to_version(&self) -> Option<Version> {
+        if self.is_complete() {
+            let [major, minor, patch, build] = self.components;
+            Some(Version::new(major, minor, patch, build))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<Version> for PartialVersion {
+    fn from(version: Version) -> Self {
+        PartialVersion {
+            components: [version.major, version.minor, version.patch, version.build],
+            len: 4,
+        }
+    }
+}
+
+impl From<Milestone> for PartialVersion {
+    fn from(milestone: Milestone) -> Self {
+        PartialVersion {
+            components: [milestone.0, 0, 0, 0],
+            len: 1,
+        }
+    }
+}
+
+impl TryFrom<PartialVersion> for Version {
+    type Error = Report<ParseVersionError>;
+
+    fn try_from(partial: PartialVersion) -> Result<Self, Self::Error> {
+        partial.to_version().ok_or_else(|| {
+            report!(ParseVersionError {
+                message: format!("Version '{partial}' does not specify all four parts."),
+            })
+        })
+    }
+}
+
+impl Display for PartialVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, component) in self.components().iter().enumerate() {
+            if index > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{component}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PartialVersion {
+    type Err = Report<ParseVersionError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_partial_version(s).map_err(|message| report!(ParseVersionError { message }))
+    }
+}
+
+impl Serialize for PartialVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PartialVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PartialVersionVisitor;
+
+        impl Visitor<'_> for PartialVersionVisitor {
+            type Value = PartialVersion;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a version string with one to four parts in dot format, like `131` or `131.0.6778`, with each part being a `u32`, or a major version as an integer")
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<PartialVersion, E>
+            where
+                E: de::Error,
+            {
+                u32::try_from(value)
+                    .map(|major| PartialVersion::from(Milestone(major)))
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<PartialVersion, E>
+            where
+                E: de::Error,
+            {
+                parse_partial_version(value).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(PartialVersionVisitor)
+    }
+}
+
+const PART_NAMES: [&str; 4] = ["major", "minor", "patch", "build"];
+
+/// Why a version string does not follow the `{major}.{minor}.{patch}.{build}` grammar.
+///
+/// Produced by a `const` parser, so that [`version!`](crate::version!) literals and parsing at
+/// runtime share one grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyntaxError {
+    MissingPart(usize),
+    InvalidPart(usize, PartError),
+    AdditionalParts,
+}
+
+/// Why a single part is no `u32`, mirroring the cases of `u32::from_str`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PartError {
+    Empty,
+    InvalidDigit,
+    Overflow,
+}
+
+impl SyntaxError {
+    /// A short static message for `const` panics, which cannot format arguments.
+    const fn panic_message(self) -> &'static str {
+        match self {
+            SyntaxError::MissingPart(_) => "Invalid version: expected four parts.",
+            SyntaxError::InvalidPart(..) => "Invalid version: a part is no valid u32.",
+            SyntaxError::AdditionalParts => "Invalid version: expected only four parts.",
+        }
+    }
+
+    /// The full message, naming the first additional part of `value` if there is one.
+    fn describe(self, value: &str) -> String {
+        match self {
+            SyntaxError::AdditionalParts => format!(
+                "{self} Got at least the additional part: {}",
+                value.split('.').nth(PART_NAMES.len()).unwrap_or_default()
+            ),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyntaxError::MissingPart(index) => {
+                write!(f, "Did not find part '{}'.", PART_NAMES[*index])
+            }
+            SyntaxError::InvalidPart(index, part_error) => write!(
+                f,
+                "Failed to parse '{}' part as an u32: {part_error}",
+                PART_NAMES[*index]
+            ),
+            SyntaxError::AdditionalParts => {
+                f.write_str("Invalid version string format. Did not expect any additional parts.")
+            }
+        }
+    }
+}
+
+impl Display for PartError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PartError::Empty => "cannot parse integer from empty string",
+            PartError::InvalidDigit => "invalid digit found in string",
+            PartError::Overflow => "number too large to fit in target type",
+        })
+    }
+}
+
+/// Parses the part `bytes[start..end]` like `u32::from_str` does.
+const fn parse_u32(bytes: &[u8], start: usize, end: usize) -> Result<u32, PartError> {
+    let mut index = start;
+    if index < end && bytes[index] == b'+' {
+        index += 1;
+    }
+    if start == end {
+        return Err(PartError::Empty);
+    }
+    if index == end {
+        return Err(PartError::InvalidDigit);
+    }
+
+    let mut value: u32 = 0;
+    while index < end {
+        let digit = bytes[index].wrapping_sub(b'0');
+        if digit > 9 {
+            return Err(PartError::InvalidDigit);
+        }
+        value = match value.checked_mul(10) {
+            Some(value) => match value.checked_add(digit as u32) {
+                Some(value) => value,
+                None => return Err(PartError::Overflow),
+            },
+            None => return Err(PartError::Overflow),
+        };
+        index += 1;
+    }
+    Ok(value)
+}
+
+/// Splits `value` at '.' and parses each part, requiring at least `required` parts. Returns the
+/// parsed components and how many there are.
+const fn parse_components(value: &str, required: usize) -> Result<([u32; 4], usize), SyntaxError> {
+    let bytes = value.as_bytes();
+    let mut components = [0; 4];
+    let mut count = 0;
+    let mut start = 0;
+    loop {
+        let mut end = start;
+        while end < bytes.len() && bytes[end] != b'.' {
+            end += 1;
+        }
+        if count == components.len() {
+            return Err(SyntaxError::AdditionalParts);
+        }
+        components[count] = match parse_u32(bytes, start, end) {
+            Ok(component) => component,
+            Err(err) => return Err(SyntaxError::InvalidPart(count, err)),
+        };
+        count += 1;
+        if end == bytes.len() {
+            break;
+        }
+        start = end + 1;
+    }
+    if count < required {
+        return Err(SyntaxError::MissingPart(count));
+    }
+    Ok((components, count))
+}
+
+const fn parse_version_const(value: &str) -> Result<Version, SyntaxError> {
+    match parse_components(value, PART_NAMES.len()) {
+        Ok(([major, minor, patch, build], _)) => Ok(Version::new(major, minor, patch, build)),
+        Err(err) => Err(err),
+    }
+}
+
 fn parse_version(value: &str) -> Result<Version, String> {
-    fn parse_part<'i>(
-        parts: &mut impl Iterator<Item = &'i str>,
-        named: &'static str,
-    ) -> Result<u32, String> {
-        parts
-            .next()
-            .ok_or_else(|| format!("Did not find part '{named}'."))?
-            .parse::<u32>()
-            .map_err(|err| format!("Failed to parse '{named}' part as an u32: {err}"))
-    }
-
-    let mut parts = value.split('.');
-    let major = parse_part(&mut parts, "major")?;
-    let minor = parse_part(&mut parts, "minor")?;
-    let patch = parse_part(&mut parts, "patch")?;
-    let build = parse_part(&mut parts, "build")?;
-
-    if let Some(next) = parts.next() {
-        return Err(format!(
-            "Invalid version string format. Did not expect any additional parts. Got at least the additional part: {next}"
-        ));
+    parse_version_const(value).map_err(|err| err.describe(value))
+}
+
+fn parse_partial_version(value: &str) -> Result<PartialVersion, String> {
+    let (components, len) = parse_components(value, 1).map_err(|err| err.describe(value))?;
+
+    Ok(PartialVersion { components, len })
+}
+
+impl Version {
+    /// Parses a version in `const` contexts, using the same grammar as `Version::from_str`.
+    ///
+    /// Prefer the [`version!`](crate::version!) macro, which guarantees evaluation at compile
+    /// time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is no valid version. In `const` contexts, this fails the compilation.
+    #[must_use]
+    pub const fn parse_const(value: &str) -> Version {
+        match parse_version_const(value) {
+            Ok(version) => version,
+            Err(err) => panic!("{}", err.panic_message()),
+        }
     }
+}
 
-    Ok(Version {
-        major,
-        minor,
-        patch,
-        build,
-    })
+/// Creates a [`Version`](crate::Version) from a string literal, validated at compile time.
+///
+/// Uses the same grammar as `Version::from_str`, so an invalid literal fails the compilation
+/// instead of panicking at runtime.
+///
+/// ```
+/// use chrome_for_testing::{Version, version};
+///
+/// const PINNED_CHROME: Version = version!("131.0.6778.204");
+///
+/// assert_eq!(PINNED_CHROME, "131.0.6778.204".parse::<Version>().unwrap());
+/// ```
+///
+/// ```compile_fail
+/// use chrome_for_testing::{Version, version};
+///
+/// const PINNED_CHROME: Version = version!("131.0.6778");
+/// ```
+#[macro_export]
+macro_rules! version {
+    ($version:literal) => {
+        const { $crate::Version::parse_const($version) }
+    };
 }
 
 impl FromStr for Version {
@@ -134,6 +682,190 @@ mod tests {
     use assertr::prelude::*;
     use std::collections::HashSet;
 
+    #[test]
+    fn match_with_reports_closest_match() {
+        let version: Version = "120.0.6099.109".parse().unwrap();
+
+        let cases = [
+            ("120.0.6099.109", Some(VersionMatch::Exact)),
+            ("120.0.6099.71", Some(VersionMatch::SameBuild)),
+            ("120.0.6045.0", Some(VersionMatch::SameMilestone)),
+            ("121.0.6099.109", None),
+        ];
+        for (other, expected) in cases {
+            assert_that!(version.match_with(&other.parse().unwrap())).is_equal_to(expected);
+        }
+    }
+
+    #[test]
+    fn milestone_and_build_helpers() {
+        let version = Version::new(120, 0, 6099, 109);
+
+        assert_that!(version.milestone()).is_equal_to(Milestone(120));
+        assert_that!(version.next_milestone()).is_equal_to(Some(Milestone(121)));
+        assert_that!(version.build_prefix()).is_equal_to(String::from("120.0.6099"));
+        assert_that!(version.same_build(&Version::new(120, 0, 6099, 71))).is_true();
+        assert_that!(version.same_build(&Version::new(120, 0, 6045, 109))).is_false();
+        assert_that!(version.same_milestone(&Version::new(120, 0, 6045, 0))).is_true();
+        assert_that!(version.same_milestone(&Version::new(121, 0, 6099, 109))).is_false();
+        assert_that!(Milestone(120).contains(&version)).is_true();
+        assert_that!(Milestone(119).next()).is_equal_to(Some(Milestone(120)));
+        assert_that!(Version::new(u32::MAX, 0, 0, 0).next_milestone()).is_none();
+        assert_that!(Milestone(u32::MAX).next()).is_none();
+    }
+
+    #[test]
+    fn milestone_serde() {
+        assert_that!(serde_json::to_string(&Milestone(120)).unwrap())
+            .is_equal_to(String::from(r#""120""#));
+        assert_that!(serde_json::from_str::<Milestone>(r#""120""#))
+            .is_ok()
+            .is_equal_to(Milestone(120));
+        assert_that!(serde_json::from_str::<Milestone>("120"))
+            .is_ok()
+            .is_equal_to(Milestone(120));
+        assert_that!(serde_json::from_str::<Milestone>(r#""120.0""#)).is_err();
+        assert_that!(serde_json::from_str::<Milestone>("4294967296")).is_err();
+        assert_that!("120".parse::<Milestone>())
+            .is_ok()
+            .is_equal_to(Milestone(120));
+        assert_that!("x".parse::<Milestone>()).is_err();
+    }
+
+    #[test]
+    fn parse_partial_versions() {
+        let cases = [
+            ("131", (131, None, None, None)),
+            ("131.0", (131, Some(0), None, None)),
+            ("131.0.6778", (131, Some(0), Some(6778), None)),
+            ("131.0.6778.204", (131, Some(0), Some(6778), Some(204))),
+        ];
+        for (input, (major, minor, patch, build)) in cases {
+            let partial: PartialVersion = input.parse().unwrap();
+
+            assert_that!(partial.major()).is_equal_to(major);
+            assert_that!(partial.minor()).is_equal_to(minor);
+            assert_that!(partial.patch()).is_equal_to(patch);
+            assert_that!(partial.build()).is_equal_to(build);
+            assert_that!(partial).has_display_value(input);
+        }
+
+        let invalid_cases = [
+            ("", "Failed to parse 'major' part as an u32"),
+            ("131.", "Failed to parse 'minor' part as an u32"),
+            ("131.x", "Failed to parse 'minor' part as an u32"),
+            ("1.2.3.4.5", "Did not expect any additional parts"),
+        ];
+        for (input, expected_error_substring) in invalid_cases {
+            assert_that!(input.parse::<PartialVersion>())
+                .is_err()
+                .derive(std::string::ToString::to_string)
+                .contains(expected_error_substring);
+        }
+    }
+
+    #[test]
+    fn partial_version_matches_versions_starting_with_its_components() {
+        let version = Version::new(131, 0, 6778, 204);
+
+        for matching in ["131", "131.0", "131.0.6778", "131.0.6778.204"] {
+            assert_that!(
+                matching
+                    .parse::<PartialVersion>()
+                    .unwrap()
+                    .matches(&version)
+            )
+            .is_true();
+        }
+        for other in ["130", "131.1", "131.0.6779", "131.0.6778.0"] {
+            assert_that!(other.parse::<PartialVersion>().unwrap().matches(&version)).is_false();
+        }
+    }
+
+    #[test]
+    fn partial_version_conversions() {
+        let version = Version::new(131, 0, 6778, 204);
+        let partial = PartialVersion::from(version);
+
+        assert_that!(partial.is_complete()).is_true();
+        assert_that!(Version::try_from(partial))
+            .is_ok()
+            .is_equal_to(version);
+        assert_that!(PartialVersion::from(Milestone(131)).to_version()).is_none();
+        assert_that!(PartialVersion::from_components(&[131, 0, 6778, 204]))
+            .is_equal_to(Some(partial));
+        assert_that!(PartialVersion::from_components(&[131]))
+            .is_equal_to(Some(Milestone(131).into()));
+        assert_that!(PartialVersion::from_components(&[])).is_none();
+        assert_that!(PartialVersion::from_components(&[1, 2, 3, 4, 5])).is_none();
+        assert_that!(Version::try_from(
+            "131.0".parse::<PartialVersion>().unwrap()
+        ))
+        .is_err()
+        .derive(std::string::ToString::to_string)
+        .contains("does not specify all four parts");
+    }
+
+    #[test]
+    fn partial_version_serde() {
+        #[derive(Debug, Deserialize)]
+        struct Config {
+            chrome: PartialVersion,
+        }
+
+        let config: Config = serde_json::from_str(r#"{ "chrome": "131" }"#).unwrap();
+        assert_that!(config.chrome).is_equal_to(PartialVersion::from(Milestone(131)));
+
+        let config: Config = serde_json::from_str(r#"{ "chrome": 131 }"#).unwrap();
+        assert_that!(config.chrome).is_equal_to(PartialVersion::from(Milestone(131)));
+
+        let partial: PartialVersion = "131.0.6778".parse().unwrap();
+        let json = serde_json::to_string(&partial).unwrap();
+        assert_that!(json.clone()).is_equal_to(String::from(r#""131.0.6778""#));
+        assert_that!(serde_json::from_str::<PartialVersion>(&json))
+            .is_ok()
+            .is_equal_to(partial);
+    }
+
+    #[test]
+    fn version_macro_evaluates_at_compile_time() {
+        const PINNED: Version = crate::version!("131.0.6778.204");
+
+        assert_that!(PINNED).is_equal_to(Version::new(131, 0, 6778, 204));
+        assert_that!(crate::version!("+1.02.3.4")).is_equal_to("+1.02.3.4".parse().unwrap());
+    }
+
+    #[test]
+    fn const_parser_matches_integer_parsing_errors() {
+        for part in [
+            "",
+            "+",
+            "-1",
+            "a",
+            "1a",
+            "4294967296",
+            "99999999999999999999",
+        ] {
+            let input = format!("{part}.0.0.0");
+            let expected = part.parse::<u32>().unwrap_err();
+
+            assert_that!(parse_version(&input))
+                .is_err()
+                .is_equal_to(format!(
+                    "Failed to parse 'major' part as an u32: {expected}"
+                ));
+        }
+        assert_that!(parse_version("1.2.3.4.5.6")).is_err().is_equal_to(String::from(
+            "Invalid version string format. Did not expect any additional parts. Got at least the additional part: 5",
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid version: expected four parts.")]
+    fn parse_const_panics_on_invalid_version() {
+        let _ = Version::parse_const(std::hint::black_box("131.0.6778"));
+    }
+
     #[test]
     fn deserialize_valid_version() {
         let test_cases = vec![
@@ -0,0 +1,698 @@
+use crate::api::binary::Binary;
+use crate::api::known_good_versions::{KnownGoodVersions, VersionWithoutChannel};
+use crate::api::platform::Platform;
+use crate::api::version::Version;
+use crate::error::Error;
+use crate::progress::NoProgress;
+#[cfg(feature = "reqwest")]
+use rootcause::prelude::ResultExt;
+use rootcause::report;
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "reqwest")]
+use std::path::Path;
+use std::path::PathBuf;
+
+/// The outcome of testing a single version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    /// The version does not show the regression.
+    Good,
+
+    /// The version shows the regression.
+    Bad,
+
+    /// The version cannot be tested, e.g. because it fails for an unrelated reason. Bisection
+    /// continues with a neighboring version.
+    Skip,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Good => "good",
+            Verdict::Bad => "bad",
+            Verdict::Skip => "skipped",
+        })
+    }
+}
+
+/// A version tested during bisection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TestedVersion {
+    /// The tested version.
+    pub version: Version,
+
+    /// The result of testing it.
+    pub verdict: Verdict,
+}
+
+/// A progress update, sent after each tested version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BisectProgress {
+    /// The version just tested.
+    pub tested: TestedVersion,
+
+    /// How many versions were tested so far, including skipped ones.
+    pub tested_count: usize,
+
+    /// How many candidates between the current bounds are left untested.
+    pub remaining: usize,
+
+    /// The newest version known to be good so far.
+    pub last_good: Version,
+
+    /// The oldest version known to be bad so far.
+    pub first_bad: Version,
+}
+
+impl BisectProgress {
+    /// Roughly how many more versions must be tested, assuming none are skipped.
+    #[must_use]
+    pub fn estimated_steps(&self) -> u32 {
+        (self.remaining + 1).next_power_of_two().trailing_zeros()
+    }
+}
+
+/// Receives progress updates during bisection, like a
+/// [`ProgressReporter`](crate::ProgressReporter) does during installation.
+///
+/// Closures taking a [`BisectProgress`] implement this trait as well. [`NoProgress`] discards
+/// all updates.
+pub trait BisectReporter {
+    /// Called after every tested version.
+    fn report(&self, progress: BisectProgress);
+}
+
+impl<F> BisectReporter for F
+where
+    F: Fn(BisectProgress),
+{
+    fn report(&self, progress: BisectProgress) {
+        self(progress);
+    }
+}
+
+impl BisectReporter for NoProgress {
+    fn report(&self, _progress: BisectProgress) {}
+}
+
+/// Installs a bisection candidate before it is tested.
+///
+/// Closures taking a [`VersionWithoutChannel`] and returning a future of the installed path
+/// implement this trait as well, e.g. to install through an HTTP backend other than `reqwest`
+/// using [`extract_archive`](crate::extract_archive).
+pub trait Installer<'a> {
+    /// Resolves and installs the download of `entry`, returning the path of the installed
+    /// executable.
+    fn install(
+        &self,
+        entry: &'a VersionWithoutChannel,
+    ) -> impl Future<Output = crate::Result<PathBuf>>;
+}
+
+impl<'a, F, Fut> Installer<'a> for F
+where
+    F: Fn(&'a VersionWithoutChannel) -> Fut,
+    Fut: Future<Output = crate::Result<PathBuf>>,
+{
+    fn install(
+        &self,
+        entry: &'a VersionWithoutChannel,
+    ) -> impl Future<Output = crate::Result<PathBuf>> {
+        self(entry)
+    }
+}
+
+/// An [`Installer`] downloading a binary with [`Download::install`](crate::Download::install)
+/// into a directory per version.
+///
+/// Versions already installed into the directory are reused, so an interrupted bisection can be
+/// resumed without downloading again. Each archive is unpacked aside and only moved into place
+/// once complete, so that an interrupted installation is never mistaken for a complete one.
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone)]
+pub struct DownloadInstaller {
+    client: reqwest::Client,
+    binary: Binary,
+    platform: Platform,
+    dir: PathBuf,
+}
+
+#[cfg(feature = "reqwest")]
+impl DownloadInstaller {
+    /// Installs `binary` for `platform` into a subdirectory of `dir` named after the version.
+    ///
+    /// Combine it with [`Bisect::require_download`] for the same binary and platform, so that
+    /// only installable versions are tested.
+    #[must_use]
+    pub fn new(
+        client: reqwest::Client,
+        binary: Binary,
+        platform: Platform,
+        dir: impl Into<PathBuf>,
+    ) -> Self {
+        DownloadInstaller {
+            client,
+            binary,
+            platform,
+            dir: dir.into(),
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl<'a> Installer<'a> for DownloadInstaller {
+    async fn install(&self, entry: &'a VersionWithoutChannel) -> crate::Result<PathBuf> {
+        let dir = self.dir.join(entry.version.to_string());
+        let executable_path = self.binary.executable_path(self.platform);
+        let executable = dir.join(executable_path);
+        if executable.is_file() {
+            return Ok(executable);
+        }
+        let download = entry.downloads.resolve(self.binary, self.platform)?;
+
+        std::fs::create_dir_all(&dir)
+            .context_to::<Error>()
+            .attach_with(|| format!("while creating '{}'", dir.display()))?;
+        let staging = tempfile::Builder::new()
+            .prefix(".staging-")
+            .tempdir_in(&dir)
+            .context_to::<Error>()
+            .attach_with(|| format!("while creating staging directory in '{}'", dir.display()))?;
+        download
+            .install(&self.client, staging.path(), &NoProgress)
+            .await?;
+
+        // The archive's top-level directory, e.g. "chrome-linux64", holds the whole binary.
+        let root = executable_path
+            .iter()
+            .next()
+            .map(Path::new)
+            .unwrap_or(executable_path);
+        let installed = dir.join(root);
+        if installed.exists() {
+            std::fs::remove_dir_all(&installed)
+                .context_to::<Error>()
+                .attach_with(|| format!("while removing incomplete '{}'", installed.display()))?;
+        }
+        std::fs::rename(staging.path().join(root), &installed)
+            .context_to::<Error>()
+            .attach_with(|| format!("while moving installation to '{}'", installed.display()))?;
+        Ok(executable)
+    }
+}
+
+/// The result of [`Bisect::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BisectOutcome {
+    /// The newest good version, directly preceding the first bad one among the candidates.
+    pub last_good: Version,
+
+    /// The first bad version. If [`BisectOutcome::skipped`] is not empty, the regression may have
+    /// been introduced by any of the skipped versions instead.
+    pub first_bad: Version,
+
+    /// Versions between `last_good` and `first_bad` that had to be skipped.
+    pub skipped: Vec<Version>,
+
+    /// All tested versions, in test order.
+    pub tested: Vec<TestedVersion>,
+}
+
+impl BisectOutcome {
+    /// Whether `first_bad` is certainly the first bad version, i.e. no version in between was
+    /// skipped.
+    #[must_use]
+    pub fn is_conclusive(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
+
+impl Display for BisectOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "First bad version: {} (last good: {}, {} versions tested)",
+            self.first_bad,
+            self.last_good,
+            self.tested.len()
+        )?;
+        if !self.is_conclusive() {
+            let skipped = self
+                .skipped
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            write!(
+                f,
+                "; the regression may also stem from the skipped versions {}",
+                skipped.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds the first known-good version showing a regression by binary search between a good and
+/// a bad version.
+///
+/// Each candidate is installed by an [`Installer`] first. The predicate then receives the
+/// known-good entry and the installed executable, and decides whether the version is
+/// [`Verdict::Good`] or [`Verdict::Bad`]. The bounds themselves are trusted and not tested.
+///
+/// ```no_run
+/// # #[cfg(feature = "reqwest")]
+/// # async fn example() -> chrome_for_testing::Result<()> {
+/// use chrome_for_testing::bisect::{Bisect, BisectProgress, DownloadInstaller, Verdict};
+/// use chrome_for_testing::{Binary, KnownGoodVersions, Platform, version};
+///
+/// let client = reqwest::Client::new();
+/// let versions = KnownGoodVersions::fetch(&client).await?;
+/// let installer = DownloadInstaller::new(client, Binary::Chrome, Platform::Linux64, "/tmp/bisect");
+/// let outcome = Bisect::new(&versions, version!("136.0.7103.113"), version!("137.0.7151.55"))
+///     .require_download(Binary::Chrome, Platform::Linux64)
+///     .run(
+///         &installer,
+///         |entry, executable| async move {
+///             // Run the tests against the Chrome at `executable`.
+///             # let passed = entry.version.patch < 7120;
+///             Ok(if passed { Verdict::Good } else { Verdict::Bad })
+///         },
+///         &|progress: BisectProgress| {
+///             println!(
+///                 "{} is {}, ~{} steps left",
+///                 progress.tested.version,
+///                 progress.tested.verdict,
+///                 progress.estimated_steps()
+///             );
+///         },
+///     )
+///     .await?;
+/// println!("{outcome}");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Bisect<'a> {
+    versions: &'a KnownGoodVersions,
+    good: Version,
+    bad: Version,
+    required_download: Option<(Binary, Platform)>,
+}
+
+impl<'a> Bisect<'a> {
+    /// Bisects over the versions of `versions` between `good` and `bad`.
+    pub fn new(versions: &'a KnownGoodVersions, good: Version, bad: Version) -> Self {
+        Bisect {
+            versions,
+            good,
+            bad,
+            required_download: None,
+        }
+    }
+
+    /// Only considers versions with a download of `binary` for `platform`.
+    pub fn require_download(mut self, binary: Binary, platform: Platform) -> Self {
+        self.required_download = Some((binary, platform));
+        self
+    }
+
+    /// The versions strictly between the good and the bad version, oldest first.
+    #[must_use]
+    pub fn candidates(&self) -> Vec<&'a VersionWithoutChannel> {
+        let mut candidates = self
+            .versions
+            .versions
+            .iter()
+            .filter(|entry| self.good < entry.version && entry.version < self.bad)
+            .filter(|entry| {
+                self.required_download.is_none_or(|(binary, platform)| {
+                    entry
+                        .downloads
+                        .binary_for_platform(binary, platform)
+                        .is_some()
+                })
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|entry| entry.version);
+        candidates.dedup_by_key(|entry| entry.version);
+        candidates
+    }
+
+    /// Runs the bisection, installing candidates with `installer`, testing them with
+    /// `predicate` and reporting each result to `reporter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidBisectRange`] if the good version is not older than the bad one,
+    /// or the first error returned by `installer` or `predicate`.
+    pub async fn run<P, F>(
+        &self,
+        installer: &impl Installer<'a>,
+        mut predicate: P,
+        reporter: &impl BisectReporter,
+    ) -> crate::Result<BisectOutcome>
+    where
+        P: FnMut(&'a VersionWithoutChannel, PathBuf) -> F,
+        F: Future<Output = crate::Result<Verdict>>,
+    {
+        if self.good >= self.bad {
+            return Err(report!(Error::InvalidBisectRange {
+                good: self.good,
+                bad: self.bad,
+            }));
+        }
+
+        let candidates = self.candidates();
+        let mut state = SearchState::new(candidates.len());
+        let mut tested = Vec::new();
+        while let Some(index) = state.next() {
+            let entry = candidates[index];
+            let executable = installer.install(entry).await?;
+            let verdict = predicate(entry, executable).await?;
+            state.record(index, verdict);
+
+            let tested_version = TestedVersion {
+                version: entry.version,
+                verdict,
+            };
+            tested.push(tested_version);
+            reporter.report(BisectProgress {
+                tested: tested_version,
+                tested_count: tested.len(),
+                remaining: state.open().len(),
+                last_good: state.last_good.map_or(self.good, |i| candidates[i].version),
+                first_bad: state.first_bad_version(&candidates).unwrap_or(self.bad),
+            });
+        }
+
+        Ok(BisectOutcome {
+            last_good: state.last_good.map_or(self.good, |i| candidates[i].version),
+            first_bad: state.first_bad_version(&candidates).unwrap_or(self.bad),
+            skipped: state
+                .skipped_between()
+                .into_iter()
+                .map(|i| candidates[i].version)
+                .collect(),
+            tested,
+        })
+    }
+}
+
+/// Binary search over candidate indices, independent of versions and I/O.
+#[derive(Debug)]
+struct SearchState {
+    /// Index of the newest good candidate, `None` for the good bound.
+    last_good: Option<usize>,
+
+    /// Index of the oldest bad candidate, `len` for the bad bound.
+    first_bad: usize,
+
+    skipped: Vec<bool>,
+}
+
+impl SearchState {
+    fn new(len: usize) -> Self {
+        SearchState {
+            last_good: None,
+            first_bad: len,
+            skipped: vec![false; len],
+        }
+    }
+
+    fn between(&self) -> std::ops::Range<usize> {
+        self.last_good.map_or(0, |i| i + 1)..self.first_bad
+    }
+
+    /// Untested candidates between the bounds.
+    fn open(&self) -> Vec<usize> {
+        self.between().filter(|&i| !self.skipped[i]).collect()
+    }
+
+    fn skipped_between(&self) -> Vec<usize> {
+        self.between().filter(|&i| self.skipped[i]).collect()
+    }
+
+    /// The open candidate closest to the middle, if any is left.
+    fn next(&self) -> Option<usize> {
+        let open = self.open();
+        open.get(open.len() / 2).copied()
+    }
+
+    fn record(&mut self, index: usize, verdict: Verdict) {
+        match verdict {
+            Verdict::Good => self.last_good = Some(index),
+            Verdict::Bad => self.first_bad = index,
+            Verdict::Skip => self.skipped[index] = true,
+        }
+    }
+
+    fn first_bad_version(&self, candidates: &[&VersionWithoutChannel]) -> Option<Version> {
+        candidates.get(self.first_bad).map(|entry| entry.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+    use std::cell::RefCell;
+
+    fn known_good_versions(builds: impl IntoIterator<Item = u32>) -> KnownGoodVersions {
+        let versions = builds
+            .into_iter()
+            .map(|build| {
+                format!(
+                    r#"{{
+                        "version": "120.0.6099.{build}",
+                        "revision": "{build}",
+                        "downloads": {{
+                            "chrome": [{{ "platform": "linux64", "url": "https://example.com/{build}" }}]
+                        }}
+                    }}"#
+                )
+            })
+            .collect::<Vec<_>>();
+        serde_json::from_str(&format!(
+            r#"{{ "timestamp": "2025-06-01T08:00:00.000Z", "versions": [{}] }}"#,
+            versions.join(",")
+        ))
+        .unwrap()
+    }
+
+    fn version(build: u32) -> Version {
+        Version::new(120, 0, 6099, build)
+    }
+
+    async fn fake_install(entry: &VersionWithoutChannel) -> crate::Result<PathBuf> {
+        Ok(PathBuf::from(format!("/opt/cft/{}/chrome", entry.version)))
+    }
+
+    #[tokio::test]
+    async fn finds_first_bad_version_in_logarithmic_steps() {
+        let versions = known_good_versions(0..100);
+        let progress = RefCell::new(Vec::new());
+
+        let outcome = Bisect::new(&versions, version(0), version(99))
+            .run(
+                &fake_install,
+                |entry, executable| async move {
+                    assert_that!(executable)
+                        .is_equal_to(PathBuf::from(format!("/opt/cft/{}/chrome", entry.version)));
+                    Ok(if entry.version.build < 37 {
+                        Verdict::Good
+                    } else {
+                        Verdict::Bad
+                    })
+                },
+                &|update: BisectProgress| progress.borrow_mut().push(update),
+            )
+            .await
+            .unwrap();
+
+        assert_that!(outcome.first_bad).is_equal_to(version(37));
+        assert_that!(outcome.last_good).is_equal_to(version(36));
+        assert_that!(outcome.is_conclusive()).is_true();
+        assert_that!(outcome.tested.len()).is_less_or_equal_to(7);
+
+        let progress = progress.into_inner();
+        assert_that!(progress.len()).is_equal_to(outcome.tested.len());
+        assert_that!(progress.last().unwrap().remaining).is_equal_to(0);
+        assert_that!(progress[0].estimated_steps()).is_less_or_equal_to(7);
+    }
+
+    #[tokio::test]
+    async fn skipped_versions_make_outcome_inconclusive() {
+        let versions = known_good_versions(0..10);
+
+        let outcome = Bisect::new(&versions, version(0), version(9))
+            .run(
+                &fake_install,
+                |entry, _| async move {
+                    Ok(match entry.version.build {
+                        0..=3 => Verdict::Good,
+                        4 | 5 => Verdict::Skip,
+                        _ => Verdict::Bad,
+                    })
+                },
+                &NoProgress,
+            )
+            .await
+            .unwrap();
+
+        assert_that!(outcome.last_good).is_equal_to(version(3));
+        assert_that!(outcome.first_bad).is_equal_to(version(6));
+        assert_that!(outcome.is_conclusive()).is_false();
+        assert_that!(outcome.skipped).is_equal_to(vec![version(4), version(5)]);
+    }
+
+    #[tokio::test]
+    async fn only_considers_versions_with_required_download() {
+        let versions = known_good_versions(0..10);
+        let bisect = Bisect::new(&versions, version(2), version(6));
+
+        assert_that!(bisect.candidates().len()).is_equal_to(3);
+        assert_that!(
+            bisect
+                .clone()
+                .require_download(Binary::ChromeDriver, Platform::Linux64)
+                .candidates()
+        )
+        .is_empty();
+
+        let outcome = bisect
+            .require_download(Binary::ChromeDriver, Platform::Linux64)
+            .run(
+                &|_| async { panic!("no candidate should be installed") },
+                |_, _| async { panic!("no candidate should be tested") },
+                &NoProgress,
+            )
+            .await
+            .unwrap();
+
+        assert_that!(outcome.first_bad).is_equal_to(version(6));
+        assert_that!(outcome.tested).is_empty();
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn download_installer_installs_once_per_version() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/chrome-linux64.zip")
+            .with_status(200)
+            .with_body(crate::api::archive::tests::chrome_archive())
+            .expect(1)
+            .create();
+        let mut versions = known_good_versions([1]);
+        versions.versions[0].downloads.chrome[0].url =
+            format!("{}/chrome-linux64.zip", server.url());
+        let dir = tempfile::tempdir().unwrap();
+        let installer = DownloadInstaller::new(
+            reqwest::Client::new(),
+            Binary::Chrome,
+            Platform::Linux64,
+            dir.path(),
+        );
+
+        let first = installer.install(&versions.versions[0]).await.unwrap();
+        let second = installer.install(&versions.versions[0]).await.unwrap();
+
+        assert_that!(first.clone())
+            .is_equal_to(dir.path().join("120.0.6099.1/chrome-linux64/chrome"));
+        assert_that!(first.is_file()).is_true();
+        assert_that!(second).is_equal_to(first);
+        mock.assert_async().await;
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn download_installer_does_not_reuse_interrupted_installation() {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        // Fails to unpack after the executable was written.
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file("chrome-linux64/chrome", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"partial").unwrap();
+        writer
+            .start_file("../escaped", SimpleFileOptions::default())
+            .unwrap();
+        let broken_archive = writer.finish().unwrap().into_inner();
+        let mut server = mockito::Server::new_async().await;
+        let broken = server
+            .mock("GET", "/broken.zip")
+            .with_status(200)
+            .with_body(broken_archive)
+            .create();
+        let complete = server
+            .mock("GET", "/chrome-linux64.zip")
+            .with_status(200)
+            .with_body(crate::api::archive::tests::chrome_archive())
+            .create();
+        let mut versions = known_good_versions([1]);
+        versions.versions[0].downloads.chrome[0].url = format!("{}/broken.zip", server.url());
+        let dir = tempfile::tempdir().unwrap();
+        let installer = DownloadInstaller::new(
+            reqwest::Client::new(),
+            Binary::Chrome,
+            Platform::Linux64,
+            dir.path(),
+        );
+
+        installer.install(&versions.versions[0]).await.unwrap_err();
+        let version_dir = dir.path().join("120.0.6099.1");
+        assert_that!(std::fs::read_dir(&version_dir).unwrap().count()).is_equal_to(0);
+
+        versions.versions[0].downloads.chrome[0].url =
+            format!("{}/chrome-linux64.zip", server.url());
+        let executable = installer.install(&versions.versions[0]).await.unwrap();
+
+        assert_that!(std::fs::read_to_string(executable).unwrap())
+            .is_equal_to(String::from("#!/bin/sh\necho chrome\n"));
+        broken.assert_async().await;
+        complete.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn rejects_inverted_range_and_propagates_predicate_errors() {
+        let versions = known_good_versions(0..10);
+
+        let err = Bisect::new(&versions, version(5), version(5))
+            .run(
+                &fake_install,
+                |_, _| async { Ok(Verdict::Good) },
+                &NoProgress,
+            )
+            .await
+            .unwrap_err();
+        let Error::InvalidBisectRange { good, bad } = err.current_context() else {
+            panic!(
+                "expected invalid bisect range error, got: {:?}",
+                err.current_context()
+            );
+        };
+        assert_that!((*good, *bad)).is_equal_to((version(5), version(5)));
+
+        let err = Bisect::new(&versions, version(0), version(9))
+            .run(
+                &fake_install,
+                |_, _| async {
+                    Err(report!(Error::InstallVerification {
+                        reason: String::from("broken download"),
+                    }))
+                },
+                &NoProgress,
+            )
+            .await
+            .unwrap_err();
+        assert_that!(matches!(
+            err.current_context(),
+            Error::InstallVerification { .. }
+        ))
+        .is_true();
+    }
+}
@@ -0,0 +1,309 @@
+use crate::api::binary::Binary;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Typed Chrome command-line flags for test runs.
+///
+/// Flags are rendered for the configured [`Binary`]: the full Chrome browser runs headless with
+/// `--headless=new`, whereas Chrome Headless Shell is always headless and does not take
+/// `--headless` at all.
+///
+/// ```no_run
+/// use chrome_for_testing::Binary;
+/// use chrome_for_testing::chrome::ChromeArgs;
+///
+/// let mut command = ChromeArgs::ci_linux_container()
+///     .binary(Binary::ChromeHeadlessShell)
+///     .remote_debugging_port(9222)
+///     .command("/opt/chrome-headless-shell-linux64/chrome-headless-shell");
+/// let child = command.spawn().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChromeArgs {
+    binary: Binary,
+    headless: bool,
+    window_size: Option<(u32, u32)>,
+    user_data_dir: Option<PathBuf>,
+    remote_debugging_port: Option<u16>,
+    remote_debugging_pipe: bool,
+    disable_gpu: bool,
+    no_sandbox: bool,
+    disable_dev_shm_usage: bool,
+    proxy_server: Option<String>,
+    lang: Option<String>,
+    extra: Vec<OsString>,
+}
+
+impl Default for ChromeArgs {
+    fn default() -> Self {
+        Self {
+            binary: Binary::Chrome,
+            headless: false,
+            window_size: None,
+            user_data_dir: None,
+            remote_debugging_port: None,
+            remote_debugging_pipe: false,
+            disable_gpu: false,
+            no_sandbox: false,
+            disable_dev_shm_usage: false,
+            proxy_server: None,
+            lang: None,
+            extra: Vec::new(),
+        }
+    }
+}
+
+impl ChromeArgs {
+    /// Creates an empty flag list for [`Binary::Chrome`], leaving all options at Chrome's
+    /// defaults.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preset for running in a Linux container on CI: headless, without the sandbox (which
+    /// requires privileges containers usually lack), without `/dev/shm` (usually too small in
+    /// containers), without GPU, with a fixed 1920x1080 window and without first-run dialogs.
+    #[must_use]
+    pub fn ci_linux_container() -> Self {
+        Self::ci().no_sandbox().disable_dev_shm_usage()
+    }
+
+    /// Preset for running on CI machines or VMs: headless, without GPU, with a fixed 1920x1080
+    /// window and without first-run dialogs.
+    #[must_use]
+    pub fn ci() -> Self {
+        Self::new()
+            .headless()
+            .disable_gpu()
+            .window_size(1920, 1080)
+            .arg("--no-first-run")
+            .arg("--no-default-browser-check")
+    }
+
+    /// Sets the binary the flags are rendered for. Defaults to [`Binary::Chrome`].
+    ///
+    /// [`Binary::ChromeDriver`] is not a browser; flags are rendered as for [`Binary::Chrome`].
+    #[must_use]
+    pub fn binary(mut self, binary: Binary) -> Self {
+        self.binary = binary;
+        self
+    }
+
+    /// Runs without a visible window: `--headless=new` for Chrome, nothing for Chrome Headless
+    /// Shell, which is always headless.
+    #[must_use]
+    pub fn headless(mut self) -> Self {
+        self.headless = true;
+        self
+    }
+
+    /// `--window-size`: the initial window size in pixels.
+    #[must_use]
+    pub fn window_size(mut self, width: u32, height: u32) -> Self {
+        self.window_size = Some((width, height));
+        self
+    }
+
+    /// `--user-data-dir`: the profile directory. Use a fresh one per test run for isolation.
+    #[must_use]
+    pub fn user_data_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.user_data_dir = Some(path.into());
+        self
+    }
+
+    /// `--remote-debugging-port`: expose the `DevTools` protocol on the given port. `0` lets the
+    /// operating system allocate a free port.
+    #[must_use]
+    pub fn remote_debugging_port(mut self, port: u16) -> Self {
+        self.remote_debugging_port = Some(port);
+        self
+    }
+
+    /// `--remote-debugging-pipe`: expose the `DevTools` protocol on file descriptors 3 and 4.
+    #[must_use]
+    pub fn remote_debugging_pipe(mut self) -> Self {
+        self.remote_debugging_pipe = true;
+        self
+    }
+
+    /// `--disable-gpu`: disable GPU hardware acceleration.
+    #[must_use]
+    pub fn disable_gpu(mut self) -> Self {
+        self.disable_gpu = true;
+        self
+    }
+
+    /// `--no-sandbox`: disable the sandbox. Only use this in environments that are isolated
+    /// otherwise, like containers.
+    #[must_use]
+    pub fn no_sandbox(mut self) -> Self {
+        self.no_sandbox = true;
+        self
+    }
+
+    /// `--disable-dev-shm-usage`: do not use `/dev/shm`, which is often too small in containers.
+    #[must_use]
+    pub fn disable_dev_shm_usage(mut self) -> Self {
+        self.disable_dev_shm_usage = true;
+        self
+    }
+
+    /// `--proxy-server`: route traffic through the given proxy, e.g. "`http://proxy:3128`".
+    #[must_use]
+    pub fn proxy_server(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy_server = Some(proxy.into());
+        self
+    }
+
+    /// `--lang`: the UI language, e.g. "en-US".
+    #[must_use]
+    pub fn lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Appends an arbitrary flag, rendered after all typed options.
+    #[must_use]
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.extra.push(arg.into());
+        self
+    }
+
+    /// Appends arbitrary flags, rendered after all typed options.
+    #[must_use]
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<OsString>>) -> Self {
+        self.extra.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub(super) fn configured_user_data_dir(&self) -> Option<&Path> {
+        self.user_data_dir.as_deref()
+    }
+
+    pub(super) fn configured_remote_debugging_port(&self) -> Option<u16> {
+        self.remote_debugging_port
+    }
+
+    /// Builds the argument vector.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<OsString> {
+        let mut args = Vec::new();
+        if self.headless && self.binary != Binary::ChromeHeadlessShell {
+            args.push(OsString::from("--headless=new"));
+        }
+        if let Some((width, height)) = self.window_size {
+            args.push(OsString::from(format!("--window-size={width},{height}")));
+        }
+        if let Some(path) = &self.user_data_dir {
+            let mut arg = OsString::from("--user-data-dir=");
+            arg.push(path);
+            args.push(arg);
+        }
+        if let Some(port) = self.remote_debugging_port {
+            args.push(OsString::from(format!("--remote-debugging-port={port}")));
+        }
+        let flags = [
+            (self.remote_debugging_pipe, "--remote-debugging-pipe"),
+            (self.disable_gpu, "--disable-gpu"),
+            (self.no_sandbox, "--no-sandbox"),
+            (self.disable_dev_shm_usage, "--disable-dev-shm-usage"),
+        ];
+        for (enabled, flag) in flags {
+            if enabled {
+                args.push(OsString::from(flag));
+            }
+        }
+        if let Some(proxy) = &self.proxy_server {
+            args.push(OsString::from(format!("--proxy-server={proxy}")));
+        }
+        if let Some(lang) = &self.lang {
+            args.push(OsString::from(format!("--lang={lang}")));
+        }
+        args.extend(self.extra.iter().cloned());
+        args
+    }
+
+    /// Builds a [`Command`] running the browser executable at `executable` with these flags.
+    #[must_use]
+    pub fn command(&self, executable: impl AsRef<OsStr>) -> Command {
+        let mut command = Command::new(executable);
+        command.args(self.to_args());
+        command
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    fn strings(args: Vec<OsString>) -> Vec<String> {
+        args.into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn empty_args_leave_defaults() {
+        assert_that!(ChromeArgs::new().to_args()).is_empty();
+    }
+
+    #[test]
+    fn all_options_are_rendered() {
+        let args = ChromeArgs::new()
+            .headless()
+            .window_size(1280, 720)
+            .user_data_dir("/tmp/profile")
+            .remote_debugging_port(9222)
+            .remote_debugging_pipe()
+            .disable_gpu()
+            .no_sandbox()
+            .disable_dev_shm_usage()
+            .proxy_server("http://proxy:3128")
+            .lang("de-DE")
+            .arg("--mute-audio")
+            .args(["--no-first-run", "--disable-extensions"]);
+
+        assert_that!(strings(args.to_args())).is_equal_to(vec![
+            String::from("--headless=new"),
+            String::from("--window-size=1280,720"),
+            String::from("--user-data-dir=/tmp/profile"),
+            String::from("--remote-debugging-port=9222"),
+            String::from("--remote-debugging-pipe"),
+            String::from("--disable-gpu"),
+            String::from("--no-sandbox"),
+            String::from("--disable-dev-shm-usage"),
+            String::from("--proxy-server=http://proxy:3128"),
+            String::from("--lang=de-DE"),
+            String::from("--mute-audio"),
+            String::from("--no-first-run"),
+            String::from("--disable-extensions"),
+        ]);
+    }
+
+    #[test]
+    fn headless_shell_does_not_take_headless_flag() {
+        let args = ChromeArgs::new()
+            .headless()
+            .binary(Binary::ChromeHeadlessShell)
+            .window_size(800, 600);
+
+        assert_that!(strings(args.to_args()))
+            .is_equal_to(vec![String::from("--window-size=800,600")]);
+    }
+
+    #[test]
+    fn ci_linux_container_preset() {
+        assert_that!(strings(ChromeArgs::ci_linux_container().to_args())).is_equal_to(vec![
+            String::from("--headless=new"),
+            String::from("--window-size=1920,1080"),
+            String::from("--disable-gpu"),
+            String::from("--no-sandbox"),
+            String::from("--disable-dev-shm-usage"),
+            String::from("--no-first-run"),
+            String::from("--no-default-browser-check"),
+        ]);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::api::version::Version;
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use rootcause::{Report, report};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+mod args;
+mod process;
+
+pub use args::ChromeArgs;
+pub use process::ChromeLauncher;
+pub use process::ChromeProcess;
+
+/// Determines the version of the Chrome (or Chrome Headless Shell) executable at `executable`.
+///
+/// Inside a macOS `.app` bundle, the version is read from the bundle's `Info.plist`. On Windows,
+/// where `chrome.exe --version` prints nothing, it is read from the executable's version resource
+/// using PowerShell. Everywhere else, `executable --version` is run and its output parsed.
+///
+/// # Errors
+///
+/// Returns an error if the executable cannot be run, or if no version can be found in its
+/// output.
+pub fn installed_version(executable: impl AsRef<Path>) -> crate::Result<Version> {
+    let executable = executable.as_ref();
+
+    if let Some(info_plist) = info_plist_path(executable)
+        && info_plist.is_file()
+    {
+        let contents = std::fs::read_to_string(&info_plist)
+            .context_to::<Error>()
+            .attach_with(|| format!("while reading '{}'", info_plist.display()))?;
+        return parse_info_plist_version(&contents)
+            .ok_or_else(|| unrecognized_version(executable, contents));
+    }
+
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("powershell");
+        command
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "(Get-Item -LiteralPath $env:CHROME_EXECUTABLE).VersionInfo.ProductVersion",
+            ])
+            .env("CHROME_EXECUTABLE", executable);
+        command
+    } else {
+        let mut command = Command::new(executable);
+        command.arg("--version");
+        command
+    };
+
+    let output = command
+        .output()
+        .context_to::<Error>()
+        .attach_with(|| format!("while querying version of '{}'", executable.display()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+
+    if !output.status.success() {
+        return Err(unrecognized_version(executable, stdout)
+            .attach(format!("process exited with {}", output.status))
+            .attach(String::from_utf8_lossy(&output.stderr).trim().to_owned()));
+    }
+
+    parse_version_output(&stdout).ok_or_else(|| unrecognized_version(executable, stdout))
+}
+
+fn unrecognized_version(executable: &Path, output: String) -> Report<Error> {
+    report!(Error::UnrecognizedChromeVersion {
+        executable: executable.to_owned(),
+        output,
+    })
+}
+
+/// Finds the first four-part version in the output of `chrome --version`, e.g. in
+/// "Google Chrome for Testing 120.0.6099.109".
+pub(crate) fn parse_version_output(output: &str) -> Option<Version> {
+    output
+        .split_whitespace()
+        .find_map(|token| token.parse::<Version>().ok())
+}
+
+/// The `Info.plist` of the `.app` bundle containing `executable`, if any.
+fn info_plist_path(executable: &Path) -> Option<PathBuf> {
+    executable
+        .ancestors()
+        .find(|ancestor| ancestor.extension().is_some_and(|ext| ext == "app"))
+        .map(|bundle| bundle.join("Contents").join("Info.plist"))
+}
+
+/// Reads `CFBundleShortVersionString` from an XML property list.
+fn parse_info_plist_version(contents: &str) -> Option<Version> {
+    let (_, rest) = contents.split_once("<key>CFBundleShortVersionString</key>")?;
+    let (_, rest) = rest.split_once("<string>")?;
+    let (value, _) = rest.split_once("</string>")?;
+    value.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    fn version(major: u32, minor: u32, patch: u32, build: u32) -> Version {
+        Version {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+
+    #[test]
+    fn parses_captured_version_outputs() {
+        let outputs = [
+            (
+                "Google Chrome for Testing 120.0.6099.109 \n",
+                version(120, 0, 6099, 109),
+            ),
+            ("Google Chrome 131.0.6778.85 \n", version(131, 0, 6778, 85)),
+            ("Chromium 126.0.6478.126 snap\n", version(126, 0, 6478, 126)),
+            (
+                "Google Chrome 127.0.6533.88 unknown\n",
+                version(127, 0, 6533, 88),
+            ),
+            ("\r\n149.0.7789.0\r\n\r\n", version(149, 0, 7789, 0)),
+        ];
+
+        for (output, expected) in outputs {
+            assert_that!(parse_version_output(output)).is_equal_to(Some(expected));
+        }
+    }
+
+    #[test]
+    fn rejects_output_without_version() {
+        assert_that!(parse_version_output("Google Chrome for Testing\n")).is_none();
+        assert_that!(parse_version_output("")).is_none();
+    }
+
+    #[test]
+    fn finds_info_plist_of_app_bundle() {
+        let executable = Path::new(
+            "/tmp/chrome-mac-arm64/Google Chrome for Testing.app/Contents/MacOS/Google Chrome for Testing",
+        );
+
+        assert_that!(info_plist_path(executable)).is_equal_to(Some(PathBuf::from(
+            "/tmp/chrome-mac-arm64/Google Chrome for Testing.app/Contents/Info.plist",
+        )));
+        assert_that!(info_plist_path(Path::new("/opt/chrome-linux64/chrome"))).is_none();
+    }
+
+    #[test]
+    fn parses_captured_info_plist() {
+        let contents = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>CFBundleExecutable</key>
+	<string>Google Chrome for Testing</string>
+	<key>CFBundleShortVersionString</key>
+	<string>120.0.6099.109</string>
+	<key>CFBundleVersion</key>
+	<string>6099.109</string>
+</dict>
+</plist>
+"#;
+
+        assert_that!(parse_info_plist_version(contents))
+            .is_equal_to(Some(version(120, 0, 6099, 109)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_unrecognized_version_output() {
+        let err = installed_version("/bin/true").unwrap_err();
+
+        // Depending on the coreutils flavor, `true --version` prints nothing or a 3-part version.
+        let Error::UnrecognizedChromeVersion { executable, .. } = err.current_context() else {
+            panic!(
+                "expected unrecognized version error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(executable.as_path()).is_equal_to(Path::new("/bin/true"));
+    }
+}
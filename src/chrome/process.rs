@@ -0,0 +1,475 @@
+use crate::api::binary::Binary;
+use crate::api::platform::Platform;
+use crate::chrome::ChromeArgs;
+use crate::diagnostics::{Diagnostics, OutputBuffer};
+use crate::error::Error;
+use crate::process::{capture_output, collect_diagnostics, startup_failure, terminate};
+use rootcause::prelude::ResultExt;
+use rootcause::report;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use url::Url;
+
+/// File Chrome writes into its user data dir once the `DevTools` endpoint accepts connections.
+/// Its first line holds the port, its second line the browser target path.
+const DEVTOOLS_ACTIVE_PORT: &str = "DevToolsActivePort";
+
+/// Prefix of the stderr line announcing the `DevTools` endpoint, e.g.
+/// "`DevTools` listening on `ws://127.0.0.1:35353/devtools/browser/5e1f...`".
+const DEVTOOLS_LISTENING: &str = "DevTools listening on ";
+
+/// Default time to wait for Chrome to announce its `DevTools` endpoint.
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often `DevToolsActivePort` is checked while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Configures and spawns a [`ChromeProcess`] exposing the Chrome `DevTools` Protocol.
+///
+/// ```no_run
+/// use chrome_for_testing::chrome::{ChromeArgs, ChromeLauncher};
+/// use chrome_for_testing::{Binary, Platform};
+///
+/// # fn main() -> chrome_for_testing::Result<()> {
+/// let chrome = ChromeLauncher::for_installation("/opt/chrome", Binary::Chrome, Platform::detect()?)
+///     .args(ChromeArgs::ci())
+///     .spawn()?;
+/// println!("Connect to {}", chrome.websocket_debugger_url());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChromeLauncher {
+    executable: PathBuf,
+    binary: Option<Binary>,
+    args: ChromeArgs,
+    startup_timeout: Duration,
+}
+
+impl ChromeLauncher {
+    /// Creates a launcher for the Chrome executable at `executable`.
+    #[must_use]
+    pub fn new(executable: impl Into<PathBuf>) -> Self {
+        Self {
+            executable: executable.into(),
+            binary: None,
+            args: ChromeArgs::new(),
+            startup_timeout: DEFAULT_STARTUP_TIMEOUT,
+        }
+    }
+
+    /// Creates a launcher for `binary`, unpacked into `install_dir` for `platform`.
+    ///
+    /// The executable is located using [`Binary::executable_path`], and the flags are always
+    /// rendered for `binary`.
+    #[must_use]
+    pub fn for_installation(
+        install_dir: impl AsRef<Path>,
+        binary: Binary,
+        platform: Platform,
+    ) -> Self {
+        Self {
+            binary: Some(binary),
+            ..Self::new(install_dir.as_ref().join(binary.executable_path(platform)))
+        }
+    }
+
+    /// Sets the flags to start Chrome with.
+    ///
+    /// Without [`ChromeArgs::remote_debugging_port`], `--remote-debugging-port=0` is passed,
+    /// letting the operating system allocate a free port. Without
+    /// [`ChromeArgs::user_data_dir`], a temporary profile directory is created and removed when
+    /// the process is dropped.
+    #[must_use]
+    pub fn args(mut self, args: ChromeArgs) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Sets how long to wait for Chrome to announce its `DevTools` endpoint.
+    #[must_use]
+    pub fn startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Spawns Chrome and waits until it announces its `DevTools` endpoint, either through the
+    /// `DevToolsActivePort` file in its user data dir or on stderr.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the temporary profile cannot be created, the executable cannot be
+    /// spawned, or if it exits or does not announce its endpoint within the startup timeout. The
+    /// error carries [`Diagnostics`] about Chrome's output.
+    pub fn spawn(&self) -> crate::Result<ChromeProcess> {
+        let mut args = self.args.clone();
+        if let Some(binary) = self.binary {
+            args = args.binary(binary);
+        }
+        if args.configured_remote_debugging_port().is_none() {
+            args = args.remote_debugging_port(0);
+        }
+        let (user_data_dir, temp_profile) = match args.configured_user_data_dir() {
+            Some(dir) => (dir.to_owned(), None),
+            None => {
+                let profile = create_temp_profile()?;
+                (profile.path().to_owned(), Some(profile))
+            }
+        };
+        args = args.user_data_dir(&user_data_dir);
+
+        // A file left behind by an earlier run would announce a stale port.
+        let active_port_file = user_data_dir.join(DEVTOOLS_ACTIVE_PORT);
+        match std::fs::remove_file(&active_port_file) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                return Err(report!(Error::Io(err))
+                    .attach(format!("while removing '{}'", active_port_file.display())));
+            }
+            _ => {}
+        }
+
+        let mut child = args
+            .command(&self.executable)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context_to::<Error>()
+            .attach_with(|| format!("while spawning Chrome: '{}'", self.executable.display()))?;
+
+        let output = Arc::new(Mutex::new(OutputBuffer::default()));
+        let receiver = capture_output(&mut child, &output);
+
+        let deadline = Instant::now() + self.startup_timeout;
+        let mut output_closed = false;
+        let websocket_debugger_url = loop {
+            if let Some(url) = read_active_port_file(&active_port_file) {
+                break url;
+            }
+            // Closed pipes usually mean an exit, but the process may keep running without them.
+            if output_closed && let Ok(Some(status)) = child.try_wait() {
+                return Err(startup_failure(
+                    &mut child,
+                    "Chrome",
+                    &output,
+                    Some(status),
+                    Error::ChromeStartup {
+                        reason: format!(
+                            "process exited before announcing its DevTools endpoint ({status})"
+                        ),
+                    },
+                ));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(startup_failure(
+                    &mut child,
+                    "Chrome",
+                    &output,
+                    None,
+                    Error::ChromeStartup {
+                        reason: format!(
+                            "no DevTools endpoint announced within {:?}",
+                            self.startup_timeout
+                        ),
+                    },
+                ));
+            }
+
+            let poll_interval = POLL_INTERVAL.min(deadline - now);
+            if output_closed {
+                std::thread::sleep(poll_interval);
+                continue;
+            }
+            match receiver.recv_timeout(poll_interval) {
+                Ok(line) => {
+                    if let Some(url) = parse_devtools_listening(&line) {
+                        break url;
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                // Keep checking the file until the process exits or the deadline passes.
+                Err(mpsc::RecvTimeoutError::Disconnected) => output_closed = true,
+            }
+        };
+
+        Ok(ChromeProcess {
+            child,
+            websocket_debugger_url,
+            user_data_dir,
+            output,
+            _temp_profile: temp_profile,
+        })
+    }
+}
+
+/// A running Chrome, killed when dropped. A temporary profile directory is removed afterwards.
+#[derive(Debug)]
+pub struct ChromeProcess {
+    child: Child,
+    websocket_debugger_url: Url,
+    user_data_dir: PathBuf,
+    output: Arc<Mutex<OutputBuffer>>,
+    // Dropped after `Drop::drop` killed the process.
+    _temp_profile: Option<TempDir>,
+}
+
+impl ChromeProcess {
+    /// The browser-level `DevTools` WebSocket URL, e.g.
+    /// "`ws://127.0.0.1:35353/devtools/browser/5e1f4c2a-...`".
+    #[must_use]
+    pub fn websocket_debugger_url(&self) -> &Url {
+        &self.websocket_debugger_url
+    }
+
+    /// The port the `DevTools` endpoint listens on.
+    #[must_use]
+    pub fn devtools_port(&self) -> u16 {
+        self.websocket_debugger_url
+            .port_or_known_default()
+            .unwrap_or_default()
+    }
+
+    /// The profile directory Chrome runs with.
+    #[must_use]
+    pub fn user_data_dir(&self) -> &Path {
+        &self.user_data_dir
+    }
+
+    /// The OS process ID.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Diagnostics about Chrome's latest output, e.g. after it crashed.
+    #[must_use]
+    pub fn diagnostics(&mut self) -> Diagnostics {
+        let exit_status = self.child.try_wait().ok().flatten();
+        collect_diagnostics("Chrome", &self.output, exit_status)
+    }
+
+    /// Kills the process and waits for it to exit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process could not be killed or waited for.
+    pub fn kill(mut self) -> crate::Result<()> {
+        terminate(&mut self.child, "Chrome")
+    }
+}
+
+impl Drop for ChromeProcess {
+    fn drop(&mut self) {
+        let _ = terminate(&mut self.child, "Chrome");
+    }
+}
+
+/// Creates a uniquely named profile directory below the system temp dir, removed on drop.
+fn create_temp_profile() -> crate::Result<TempDir> {
+    tempfile::Builder::new()
+        .prefix("chrome-for-testing-profile-")
+        .tempdir()
+        .context_to::<Error>()
+        .attach("while creating temporary profile")
+}
+
+/// Reads the `DevTools` WebSocket URL from a complete `DevToolsActivePort` file.
+fn read_active_port_file(path: &Path) -> Option<Url> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let mut lines = contents.lines();
+    let port = lines.next()?.trim().parse::<u16>().ok()?;
+    let target = lines.next()?.trim();
+    if port == 0 || !target.starts_with('/') {
+        return None;
+    }
+    Url::parse(&format!("ws://127.0.0.1:{port}{target}")).ok()
+}
+
+fn parse_devtools_listening(line: &str) -> Option<Url> {
+    let (_, url) = line.split_once(DEVTOOLS_LISTENING)?;
+    Url::parse(url.trim()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn parses_devtools_listening_line() {
+        let url = parse_devtools_listening(
+            "\nDevTools listening on ws://127.0.0.1:35353/devtools/browser/5e1f4c2a-9d6c-4f8a-8e2b-0e4f1c0b9a11",
+        );
+
+        assert_that!(url.map(String::from)).is_equal_to(Some(String::from(
+            "ws://127.0.0.1:35353/devtools/browser/5e1f4c2a-9d6c-4f8a-8e2b-0e4f1c0b9a11",
+        )));
+        assert_that!(parse_devtools_listening(
+            "[1019/120000.000:INFO] something else"
+        ))
+        .is_none();
+    }
+
+    #[test]
+    fn for_installation_locates_executable() {
+        let launcher = ChromeLauncher::for_installation(
+            "/opt/cft",
+            Binary::ChromeHeadlessShell,
+            Platform::Linux64,
+        );
+
+        assert_that!(launcher.executable).is_equal_to(PathBuf::from(
+            "/opt/cft/chrome-headless-shell-linux64/chrome-headless-shell",
+        ));
+        assert_that!(launcher.binary).is_equal_to(Some(Binary::ChromeHeadlessShell));
+    }
+
+    #[cfg(unix)]
+    mod stub_chrome {
+        use super::*;
+        use crate::diagnostics::KnownIssue;
+        use std::os::unix::fs::PermissionsExt;
+
+        const WRITE_ACTIVE_PORT_FILE: &str = r#"for arg in "$@"; do
+  case "$arg" in --user-data-dir=*) dir="${arg#--user-data-dir=}";; esac
+done
+printf '9444\n/devtools/browser/b0a1\n' > "$dir/DevToolsActivePort"
+exec sleep 30"#;
+
+        fn stub_chrome(dir: &tempfile::TempDir, script: &str) -> PathBuf {
+            let path = dir.path().join("chrome");
+            std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        }
+
+        #[test]
+        fn reads_devtools_active_port_file_and_removes_temp_profile() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = stub_chrome(&dir, WRITE_ACTIVE_PORT_FILE);
+
+            let process = ChromeLauncher::new(executable).spawn().unwrap();
+            let user_data_dir = process.user_data_dir().to_owned();
+
+            assert_that!(process.websocket_debugger_url().as_str())
+                .is_equal_to("ws://127.0.0.1:9444/devtools/browser/b0a1");
+            assert_that!(process.devtools_port()).is_equal_to(9444);
+            assert_that!(user_data_dir.is_dir()).is_true();
+            assert_that!(
+                user_data_dir
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .as_ref()
+            )
+            .starts_with("chrome-for-testing-profile-");
+
+            drop(process);
+
+            assert_that!(user_data_dir.exists()).is_false();
+        }
+
+        #[test]
+        fn keeps_configured_user_data_dir() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = stub_chrome(&dir, WRITE_ACTIVE_PORT_FILE);
+            let profile = dir.path().join("profile");
+            std::fs::create_dir(&profile).unwrap();
+
+            let process = ChromeLauncher::new(executable)
+                .args(ChromeArgs::new().user_data_dir(&profile))
+                .spawn()
+                .unwrap();
+            drop(process);
+
+            assert_that!(profile.is_dir()).is_true();
+        }
+
+        #[test]
+        fn parses_devtools_listening_line_on_stderr() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = stub_chrome(
+                &dir,
+                r#"echo "DevTools listening on ws://127.0.0.1:9333/devtools/browser/c2d3" >&2
+exec sleep 30"#,
+            );
+
+            let process = ChromeLauncher::new(executable).spawn().unwrap();
+
+            assert_that!(process.websocket_debugger_url().as_str())
+                .is_equal_to("ws://127.0.0.1:9333/devtools/browser/c2d3");
+        }
+
+        #[test]
+        fn reports_early_exit_with_output() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = stub_chrome(
+                &dir,
+                r#"echo "Missing X server or \$DISPLAY" >&2
+exit 1"#,
+            );
+
+            let err = ChromeLauncher::new(executable).spawn().unwrap_err();
+
+            let Error::ChromeStartup { reason } = err.current_context() else {
+                panic!(
+                    "expected Chrome startup error, got: {:?}",
+                    err.current_context()
+                );
+            };
+
+            assert_that!(reason.as_str())
+                .starts_with("process exited before announcing its DevTools endpoint");
+            let diagnostics = Diagnostics::from_report(&err).unwrap();
+            assert_that!(diagnostics.exit_status.as_deref()).is_equal_to(Some("exit status: 1"));
+            assert_that!(diagnostics.findings[0].issue.clone()).is_equal_to(KnownIssue::NoDisplay);
+            assert_that!(format!("{err:?}")).contains("Missing X server");
+        }
+
+        #[test]
+        fn reports_startup_timeout() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = stub_chrome(&dir, "exec sleep 30");
+
+            let err = ChromeLauncher::new(executable)
+                .startup_timeout(Duration::from_millis(200))
+                .spawn()
+                .unwrap_err();
+
+            let Error::ChromeStartup { reason } = err.current_context() else {
+                panic!(
+                    "expected Chrome startup error, got: {:?}",
+                    err.current_context()
+                );
+            };
+
+            assert_that!(reason.as_str()).starts_with("no DevTools endpoint announced");
+        }
+
+        #[test]
+        fn reports_startup_timeout_after_output_is_closed() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = stub_chrome(&dir, "exec sleep 30 >&- 2>&-");
+
+            let started = Instant::now();
+            let err = ChromeLauncher::new(executable)
+                .startup_timeout(Duration::from_millis(200))
+                .spawn()
+                .unwrap_err();
+
+            let Error::ChromeStartup { reason } = err.current_context() else {
+                panic!(
+                    "expected Chrome startup error, got: {:?}",
+                    err.current_context()
+                );
+            };
+
+            assert_that!(reason.as_str()).starts_with("no DevTools endpoint announced");
+            assert_that!(started.elapsed() < Duration::from_secs(10)).is_true();
+        }
+    }
+}
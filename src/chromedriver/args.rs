@@ -0,0 +1,301 @@
+use crate::chromedriver::LogLevel;
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Typed `ChromeDriver` command-line arguments.
+///
+/// ```no_run
+/// use chrome_for_testing::chromedriver::{ChromeDriverArgs, LogLevel};
+///
+/// let mut command = ChromeDriverArgs::new()
+///     .port(9515)
+///     .log_level(LogLevel::Debug)
+///     .log_path("/tmp/chromedriver.log")
+///     .command("/opt/chromedriver-linux64/chromedriver");
+/// let child = command.spawn().unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChromeDriverArgs {
+    port: Option<u16>,
+    adb_port: Option<u16>,
+    log_path: Option<PathBuf>,
+    log_level: Option<LogLevel>,
+    verbosity: Option<Verbosity>,
+    append_log: bool,
+    replayable: bool,
+    allowed_ips: Vec<String>,
+    allowed_origins: Vec<String>,
+    readable_timestamp: bool,
+    enable_chrome_logs: bool,
+    disable_dev_shm_usage: bool,
+    url_base: Option<String>,
+}
+
+/// The mutually exclusive `--verbose` and `--silent` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verbosity {
+    Verbose,
+    Silent,
+}
+
+impl ChromeDriverArgs {
+    /// Creates an empty argument list, leaving all options at `ChromeDriver`'s defaults.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `--port`: the port to listen on. `0` lets the operating system allocate a free port.
+    #[must_use]
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// `--adb-port`: the port of the Android Debug Bridge server.
+    #[must_use]
+    pub fn adb_port(mut self, port: u16) -> Self {
+        self.adb_port = Some(port);
+        self
+    }
+
+    /// `--log-path`: write the log to the file at `path` instead of stderr.
+    #[must_use]
+    pub fn log_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.log_path = Some(path.into());
+        self
+    }
+
+    /// `--log-level`: the minimum level of logged messages.
+    #[must_use]
+    pub fn log_level(mut self, log_level: LogLevel) -> Self {
+        self.log_level = Some(log_level);
+        self
+    }
+
+    /// `--verbose`: log verbosely, equivalent to `--log-level=ALL`. Overrides
+    /// [`ChromeDriverArgs::silent`].
+    #[must_use]
+    pub fn verbose(mut self) -> Self {
+        self.verbosity = Some(Verbosity::Verbose);
+        self
+    }
+
+    /// `--silent`: log nothing, equivalent to `--log-level=OFF`. Overrides
+    /// [`ChromeDriverArgs::verbose`].
+    #[must_use]
+    pub fn silent(mut self) -> Self {
+        self.verbosity = Some(Verbosity::Silent);
+        self
+    }
+
+    /// `--append-log`: append to the log file instead of truncating it.
+    #[must_use]
+    pub fn append_log(mut self) -> Self {
+        self.append_log = true;
+        self
+    }
+
+    /// `--replayable`: log verbosely without truncating long strings, so that the log can be
+    /// replayed.
+    #[must_use]
+    pub fn replayable(mut self) -> Self {
+        self.replayable = true;
+        self
+    }
+
+    /// `--allowed-ips`: allow remote connections from the given IP address. May be given
+    /// multiple times. By default, only local connections are allowed.
+    #[must_use]
+    pub fn allowed_ip(mut self, ip: impl Into<String>) -> Self {
+        self.allowed_ips.push(ip.into());
+        self
+    }
+
+    /// `--allowed-origins`: allow requests from the given origin, e.g. "`http://localhost:3000`".
+    /// May be given multiple times.
+    #[must_use]
+    pub fn allowed_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    /// `--readable-timestamp`: add human-readable timestamps to the log.
+    #[must_use]
+    pub fn readable_timestamp(mut self) -> Self {
+        self.readable_timestamp = true;
+        self
+    }
+
+    /// `--enable-chrome-logs`: show the logs of the browser.
+    #[must_use]
+    pub fn enable_chrome_logs(mut self) -> Self {
+        self.enable_chrome_logs = true;
+        self
+    }
+
+    /// `--disable-dev-shm-usage`: do not use `/dev/shm`, which is often too small in containers.
+    #[must_use]
+    pub fn disable_dev_shm_usage(mut self) -> Self {
+        self.disable_dev_shm_usage = true;
+        self
+    }
+
+    /// `--url-base`: the base path of all WebDriver endpoints, e.g. "`/wd/hub`".
+    #[must_use]
+    pub fn url_base(mut self, url_base: impl Into<String>) -> Self {
+        self.url_base = Some(url_base.into());
+        self
+    }
+
+    pub(super) fn configured_port(&self) -> Option<u16> {
+        self.port
+    }
+
+    pub(super) fn configured_url_base(&self) -> Option<&str> {
+        self.url_base.as_deref()
+    }
+
+    /// Builds the argument vector.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<OsString> {
+        let mut args = Vec::new();
+        if let Some(port) = self.port {
+            args.push(OsString::from(format!("--port={port}")));
+        }
+        if let Some(port) = self.adb_port {
+            args.push(OsString::from(format!("--adb-port={port}")));
+        }
+        if let Some(path) = &self.log_path {
+            let mut arg = OsString::from("--log-path=");
+            arg.push(path);
+            args.push(arg);
+        }
+        if let Some(log_level) = self.log_level {
+            args.push(OsString::from(format!("--log-level={log_level}")));
+        }
+        match self.verbosity {
+            Some(Verbosity::Verbose) => args.push(OsString::from("--verbose")),
+            Some(Verbosity::Silent) => args.push(OsString::from("--silent")),
+            None => {}
+        }
+        let flags = [
+            (self.append_log, "--append-log"),
+            (self.replayable, "--replayable"),
+        ];
+        for (enabled, flag) in flags {
+            if enabled {
+                args.push(OsString::from(flag));
+            }
+        }
+        if !self.allowed_ips.is_empty() {
+            args.push(OsString::from(format!(
+                "--allowed-ips={}",
+                self.allowed_ips.join(",")
+            )));
+        }
+        if !self.allowed_origins.is_empty() {
+            args.push(OsString::from(format!(
+                "--allowed-origins={}",
+                self.allowed_origins.join(",")
+            )));
+        }
+        let flags = [
+            (self.readable_timestamp, "--readable-timestamp"),
+            (self.enable_chrome_logs, "--enable-chrome-logs"),
+            (self.disable_dev_shm_usage, "--disable-dev-shm-usage"),
+        ];
+        for (enabled, flag) in flags {
+            if enabled {
+                args.push(OsString::from(flag));
+            }
+        }
+        if let Some(url_base) = &self.url_base {
+            args.push(OsString::from(format!("--url-base={url_base}")));
+        }
+        args
+    }
+
+    /// Builds a [`Command`] running the `ChromeDriver` executable at `executable` with these
+    /// arguments.
+    #[must_use]
+    pub fn command(&self, executable: impl AsRef<OsStr>) -> Command {
+        let mut command = Command::new(executable);
+        command.args(self.to_args());
+        command
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    fn strings(args: Vec<OsString>) -> Vec<String> {
+        args.into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn empty_args_leave_defaults() {
+        assert_that!(ChromeDriverArgs::new().to_args()).is_empty();
+    }
+
+    #[test]
+    fn all_options_are_rendered() {
+        let args = ChromeDriverArgs::new()
+            .port(4444)
+            .adb_port(5037)
+            .log_path("/tmp/chromedriver.log")
+            .log_level(LogLevel::Debug)
+            .verbose()
+            .append_log()
+            .replayable()
+            .allowed_ip("10.0.0.1")
+            .allowed_ip("10.0.0.2")
+            .allowed_origin("http://localhost:3000")
+            .readable_timestamp()
+            .enable_chrome_logs()
+            .disable_dev_shm_usage()
+            .url_base("/wd/hub");
+
+        assert_that!(strings(args.to_args())).is_equal_to(vec![
+            String::from("--port=4444"),
+            String::from("--adb-port=5037"),
+            String::from("--log-path=/tmp/chromedriver.log"),
+            String::from("--log-level=DEBUG"),
+            String::from("--verbose"),
+            String::from("--append-log"),
+            String::from("--replayable"),
+            String::from("--allowed-ips=10.0.0.1,10.0.0.2"),
+            String::from("--allowed-origins=http://localhost:3000"),
+            String::from("--readable-timestamp"),
+            String::from("--enable-chrome-logs"),
+            String::from("--disable-dev-shm-usage"),
+            String::from("--url-base=/wd/hub"),
+        ]);
+    }
+
+    #[test]
+    fn verbose_and_silent_override_each_other() {
+        let silent = ChromeDriverArgs::new().verbose().silent();
+        let verbose = ChromeDriverArgs::new().silent().verbose();
+
+        assert_that!(strings(silent.to_args())).is_equal_to(vec![String::from("--silent")]);
+        assert_that!(strings(verbose.to_args())).is_equal_to(vec![String::from("--verbose")]);
+    }
+
+    #[test]
+    fn command_uses_executable_and_args() {
+        let command = ChromeDriverArgs::new()
+            .port(9515)
+            .command("/opt/chromedriver");
+
+        assert_that!(command.get_program().to_string_lossy().into_owned())
+            .is_equal_to(String::from("/opt/chromedriver"));
+        assert_that!(strings(command.get_args().map(OsString::from).collect()))
+            .is_equal_to(vec![String::from("--port=9515")]);
+    }
+}
@@ -0,0 +1,550 @@
+use crate::chromedriver::LogLevel;
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use std::collections::{HashMap, VecDeque};
+use std::io::BufRead;
+use std::sync::LazyLock;
+use time::format_description::OwnedFormatItem;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+/// The format of timestamps written with `--readable-timestamp`, e.g. "10-11-2025 14:03:27.123456".
+static READABLE_TIMESTAMP_FORMAT: LazyLock<OwnedFormatItem> = LazyLock::new(|| {
+    time::format_description::parse_owned::<2>(
+        "[month]-[day]-[year] [hour]:[minute]:[second].[subsecond]",
+    )
+    .expect("valid format description")
+});
+
+/// A parsed `ChromeDriver` log, e.g. as written with `--log-level=ALL`.
+///
+/// Each line starting with a `[timestamp][LEVEL]: ` header starts a new [`LogRecord`]; all other
+/// lines (like pretty-printed JSON payloads or Chrome's own output) continue the previous record.
+/// Lines before the first header are ignored.
+///
+/// ```
+/// use chrome_for_testing::chromedriver::ChromeDriverLog;
+///
+/// let log = ChromeDriverLog::parse(
+///     "[1760191407.112][INFO]: [9f3c] COMMAND FindElement {\n   \"using\": \"css selector\",\n   \"value\": \"#submit\"\n}\n\
+///      [1760191407.289][INFO]: [9f3c] RESPONSE FindElement ERROR no such element: Unable to locate element\n",
+/// );
+/// let failed = log.failed_exchanges().next().unwrap();
+/// assert_eq!(failed.command.raw.lines().count(), 4);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChromeDriverLog {
+    /// The records, in log order.
+    pub records: Vec<LogRecord>,
+}
+
+/// A single record of a `ChromeDriver` log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    /// The 1-based number of the line the record starts at.
+    pub line: usize,
+
+    /// When the record was logged.
+    pub timestamp: LogTimestamp,
+
+    /// The level the record was logged with.
+    pub level: LogLevel,
+
+    /// The WebDriver session the record belongs to, if any.
+    pub session_id: Option<String>,
+
+    /// The parsed content of the record.
+    pub entry: LogEntry,
+
+    /// The unparsed text of the record, including its header and continuation lines.
+    pub raw: String,
+}
+
+/// The timestamp of a [`LogRecord`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogTimestamp {
+    /// The default format: seconds since the Unix epoch, e.g. "1760191407.112".
+    Unix(OffsetDateTime),
+
+    /// The `--readable-timestamp` format, in local time without a UTC offset.
+    Readable(PrimitiveDateTime),
+}
+
+/// The parsed content of a [`LogRecord`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogEntry {
+    /// A WebDriver command received by `ChromeDriver`.
+    Command {
+        /// The command name, e.g. "`FindElement`".
+        name: String,
+
+        /// The command parameters, usually pretty-printed JSON.
+        payload: String,
+    },
+
+    /// The response to a WebDriver command.
+    Response {
+        /// The command name, e.g. "`FindElement`".
+        name: String,
+
+        /// The response value, usually pretty-printed JSON. Empty if the command failed.
+        payload: String,
+
+        /// The error message if the command failed, e.g. "no such element: ...".
+        error: Option<String>,
+    },
+
+    /// A message exchanged with Chrome over the `DevTools` protocol.
+    DevTools {
+        /// Whether this is a command, response or event.
+        kind: DevToolsKind,
+
+        /// The protocol method, e.g. "`Page.navigate`".
+        method: String,
+
+        /// The message id pairing commands and responses; events have none.
+        id: Option<u64>,
+
+        /// The `DevTools` session, if any.
+        session_id: Option<String>,
+
+        /// The `DevTools` target, if any.
+        target: Option<String>,
+
+        /// The message parameters or result, usually pretty-printed JSON.
+        payload: String,
+    },
+
+    /// Any other message.
+    Message(String),
+}
+
+/// The kind of a [`LogEntry::DevTools`] message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DevToolsKind {
+    /// A command sent to Chrome.
+    Command,
+
+    /// The response to a command.
+    Response,
+
+    /// An event sent by Chrome.
+    Event,
+}
+
+/// A WebDriver command paired with its response.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommandExchange<'a> {
+    /// The [`LogEntry::Command`] record.
+    pub command: &'a LogRecord,
+
+    /// The [`LogEntry::Response`] record, if the log contains it.
+    pub response: Option<&'a LogRecord>,
+}
+
+impl CommandExchange<'_> {
+    /// The error message of the response, if the command failed.
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        match self.response.map(|response| &response.entry) {
+            Some(LogEntry::Response {
+                error: Some(error), ..
+            }) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl ChromeDriverLog {
+    /// Parses a complete log.
+    #[must_use]
+    pub fn parse(log: &str) -> Self {
+        let mut builder = Builder::default();
+        for line in log.lines() {
+            builder.push_line(line);
+        }
+        builder.finish()
+    }
+
+    /// Parses a log read line by line from `reader`, e.g. a log file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails or the log is not valid UTF-8.
+    pub fn read(reader: impl BufRead) -> crate::Result<Self> {
+        let mut builder = Builder::default();
+        for line in reader.lines() {
+            let line = line.context_to::<Error>().attach_with(|| {
+                format!("while reading ChromeDriver log line {}", builder.line + 1)
+            })?;
+            builder.push_line(&line);
+        }
+        Ok(builder.finish())
+    }
+
+    /// The records `ChromeDriver` would have written when started with `--log-level=level`.
+    pub fn filtered(&self, level: LogLevel) -> impl Iterator<Item = &LogRecord> {
+        self.records
+            .iter()
+            .filter(move |record| level.includes(record.level))
+    }
+
+    /// Pairs each WebDriver command with its response.
+    ///
+    /// Responses are matched to the oldest unanswered command with the same name in the same
+    /// session.
+    #[must_use]
+    pub fn exchanges(&self) -> Vec<CommandExchange<'_>> {
+        let mut exchanges = Vec::new();
+        let mut pending = HashMap::<(Option<&str>, &str), VecDeque<usize>>::new();
+        for record in &self.records {
+            let session_id = record.session_id.as_deref();
+            match &record.entry {
+                LogEntry::Command { name, .. } => {
+                    pending
+                        .entry((session_id, name))
+                        .or_default()
+                        .push_back(exchanges.len());
+                    exchanges.push(CommandExchange {
+                        command: record,
+                        response: None,
+                    });
+                }
+                LogEntry::Response { name, .. } => {
+                    if let Some(index) = pending
+                        .get_mut(&(session_id, name.as_str()))
+                        .and_then(VecDeque::pop_front)
+                    {
+                        exchanges[index].response = Some(record);
+                    }
+                }
+                LogEntry::DevTools { .. } | LogEntry::Message(_) => {}
+            }
+        }
+        exchanges
+    }
+
+    /// The WebDriver commands which failed, in log order.
+    pub fn failed_exchanges(&self) -> impl Iterator<Item = CommandExchange<'_>> {
+        self.exchanges()
+            .into_iter()
+            .filter(|exchange| exchange.error().is_some())
+    }
+}
+
+/// Collects lines into records.
+#[derive(Default)]
+struct Builder {
+    records: Vec<LogRecord>,
+    current: Option<(Header, String)>,
+    line: usize,
+}
+
+struct Header {
+    line: usize,
+    timestamp: LogTimestamp,
+    level: LogLevel,
+
+    /// The byte offset of the message following the header.
+    message_start: usize,
+}
+
+impl Builder {
+    fn push_line(&mut self, line: &str) {
+        self.line += 1;
+        if let Some(header) = parse_header(line, self.line) {
+            self.flush();
+            self.current = Some((header, String::from(line)));
+        } else if let Some((_, raw)) = &mut self.current {
+            raw.push('\n');
+            raw.push_str(line);
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some((header, raw)) = self.current.take() {
+            let (session_id, message) = split_session_id(&raw[header.message_start..]);
+            let entry = parse_entry(message);
+            self.records.push(LogRecord {
+                line: header.line,
+                timestamp: header.timestamp,
+                level: header.level,
+                session_id: session_id.map(String::from),
+                entry,
+                raw,
+            });
+        }
+    }
+
+    fn finish(mut self) -> ChromeDriverLog {
+        self.flush();
+        ChromeDriverLog {
+            records: self.records,
+        }
+    }
+}
+
+/// Parses a `[timestamp][LEVEL]: ` header.
+fn parse_header(line: &str, line_number: usize) -> Option<Header> {
+    let rest = line.strip_prefix('[')?;
+    let (timestamp, rest) = rest.split_once("][")?;
+    let (level, message) = rest.split_once("]: ")?;
+    Some(Header {
+        line: line_number,
+        timestamp: parse_timestamp(timestamp)?,
+        level: level.parse().ok()?,
+        message_start: line.len() - message.len(),
+    })
+}
+
+fn parse_timestamp(timestamp: &str) -> Option<LogTimestamp> {
+    if let Some((seconds, fraction)) = timestamp.split_once('.')
+        && let Ok(seconds) = seconds.parse::<i64>()
+        && (1..=9).contains(&fraction.len())
+        && fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        // Right-pad the fraction to nine digits, e.g. "112" to 112_000_000 nanoseconds.
+        let nanos: i128 = format!("{fraction:0<9}").parse().ok()?;
+        return OffsetDateTime::from_unix_timestamp_nanos(
+            i128::from(seconds) * 1_000_000_000 + nanos,
+        )
+        .ok()
+        .map(LogTimestamp::Unix);
+    }
+    PrimitiveDateTime::parse(timestamp, &*READABLE_TIMESTAMP_FORMAT)
+        .ok()
+        .map(LogTimestamp::Readable)
+}
+
+/// Splits a leading `[session id] ` off a message.
+fn split_session_id(message: &str) -> (Option<&str>, &str) {
+    if let Some(rest) = message.strip_prefix('[')
+        && let Some((session_id, rest)) = rest.split_once("] ")
+        && !session_id.is_empty()
+        && session_id.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return (Some(session_id), rest);
+    }
+    (None, message)
+}
+
+fn parse_entry(message: &str) -> LogEntry {
+    if let Some(rest) = message.strip_prefix("COMMAND ") {
+        let (name, payload) = split_word(rest);
+        return LogEntry::Command {
+            name: String::from(name),
+            payload: String::from(payload),
+        };
+    }
+    if let Some(rest) = message.strip_prefix("RESPONSE ") {
+        let (name, payload) = split_word(rest);
+        return match payload.strip_prefix("ERROR") {
+            Some(error) => LogEntry::Response {
+                name: String::from(name),
+                payload: String::new(),
+                error: Some(String::from(error.trim())),
+            },
+            None => LogEntry::Response {
+                name: String::from(name),
+                payload: String::from(payload),
+                error: None,
+            },
+        };
+    }
+    let devtools = [
+        ("DevTools WebSocket Command: ", DevToolsKind::Command),
+        ("DevTools WebSocket Response: ", DevToolsKind::Response),
+        ("DevTools WebSocket Event: ", DevToolsKind::Event),
+    ];
+    for (prefix, kind) in devtools {
+        if let Some(rest) = message.strip_prefix(prefix) {
+            return parse_devtools(kind, rest);
+        }
+    }
+    LogEntry::Message(String::from(message))
+}
+
+/// Parses e.g. "`Page.navigate (id=12) (session_id=AB12) 5E1F {...}`".
+fn parse_devtools(kind: DevToolsKind, message: &str) -> LogEntry {
+    let (method, mut rest) = split_word(message);
+    let mut id = None;
+    let mut session_id = None;
+    while let Some(group) = rest.strip_prefix('(')
+        && let Some((group, remainder)) = group.split_once(')')
+    {
+        if let Some(value) = group.strip_prefix("id=") {
+            id = value.parse().ok();
+        } else if let Some(value) = group.strip_prefix("session_id=") {
+            session_id = Some(String::from(value));
+        }
+        rest = remainder.trim_start();
+    }
+    let target = match rest.chars().next() {
+        Some(c) if c.is_ascii_alphanumeric() => {
+            let (target, payload) = split_word(rest);
+            rest = payload;
+            Some(String::from(target))
+        }
+        _ => None,
+    };
+    LogEntry::DevTools {
+        kind,
+        method: String::from(method),
+        id,
+        session_id,
+        target,
+        payload: String::from(rest),
+    }
+}
+
+/// Splits `text` at its first whitespace, trimming the remainder.
+fn split_word(text: &str) -> (&str, &str) {
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim()),
+        None => (text, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+    use time::macros::datetime;
+
+    const LOG: &str = r##"Starting ChromeDriver 141.0.7390.76 on port 9515
+[1760191406.981][INFO]: Starting ChromeDriver 141.0.7390.76 on port 9515
+[1760191407.003][INFO]: [9f3c0e8a] COMMAND InitSession {
+   "capabilities": {
+      "alwaysMatch": {
+         "browserName": "chrome"
+      }
+   }
+}
+[1760191407.105][DEBUG]: DevTools WebSocket Command: Page.navigate (id=12) (session_id=A1B2) 5E1F0C {
+   "url": "https://example.com/"
+}
+[1760191407.2][DEBUG]: DevTools WebSocket Event: Page.frameStartedLoading (session_id=A1B2) 5E1F0C {
+   "frameId": "5E1F0C"
+}
+[1760191407.310][DEBUG]: DevTools WebSocket Response: Page.navigate (id=12) (session_id=A1B2) 5E1F0C {
+   "frameId": "5E1F0C"
+}
+[1760191407.322][INFO]: [9f3c0e8a] RESPONSE InitSession {
+   "sessionId": "9f3c0e8a"
+}
+[1760191407.401][INFO]: [9f3c0e8a] COMMAND FindElement {
+   "using": "css selector",
+   "value": "#submit"
+}
+[1760191407.412][INFO]: [9f3c0e8a] COMMAND GetTitle {
+}
+[1760191407.489][INFO]: [9f3c0e8a] RESPONSE FindElement ERROR no such element: Unable to locate element: {"method":"css selector","selector":"#submit"}
+  (Session info: chrome=141.0.7390.76)
+[1760191407.502][WARNING]: [9f3c0e8a] COMMAND DeleteSession {
+}
+"##;
+
+    #[test]
+    fn parses_default_format_records() {
+        let log = ChromeDriverLog::parse(LOG);
+
+        assert_that!(log.records.len()).is_equal_to(10);
+
+        let first = &log.records[0];
+        assert_that!(first.line).is_equal_to(2);
+        assert_that!(first.timestamp)
+            .is_equal_to(LogTimestamp::Unix(datetime!(2025-10-11 14:03:26.981 UTC)));
+        assert_that!(first.level).is_equal_to(LogLevel::Info);
+        assert_that!(first.session_id.clone()).is_none();
+        assert_that!(first.entry.clone()).is_equal_to(LogEntry::Message(String::from(
+            "Starting ChromeDriver 141.0.7390.76 on port 9515",
+        )));
+
+        let init_session = &log.records[1];
+        assert_that!(init_session.session_id.clone()).is_equal_to(Some(String::from("9f3c0e8a")));
+        let LogEntry::Command { name, payload } = &init_session.entry else {
+            panic!("expected command, got: {:?}", init_session.entry);
+        };
+        assert_that!(name.as_str()).is_equal_to("InitSession");
+        assert_that!(serde_json::from_str::<serde_json::Value>(payload).unwrap()["capabilities"]["alwaysMatch"]["browserName"].clone())
+            .is_equal_to(serde_json::json!("chrome"));
+        assert_that!(init_session.raw.lines().count()).is_equal_to(7);
+
+        assert_that!(log.records[2].entry.clone()).is_equal_to(LogEntry::DevTools {
+            kind: DevToolsKind::Command,
+            method: String::from("Page.navigate"),
+            id: Some(12),
+            session_id: Some(String::from("A1B2")),
+            target: Some(String::from("5E1F0C")),
+            payload: String::from("{\n   \"url\": \"https://example.com/\"\n}"),
+        });
+        assert_that!(log.records[3].timestamp)
+            .is_equal_to(LogTimestamp::Unix(datetime!(2025-10-11 14:03:27.2 UTC)));
+        let LogEntry::DevTools { kind, id, .. } = &log.records[3].entry else {
+            panic!("expected DevTools message, got: {:?}", log.records[3].entry);
+        };
+        assert_that!(*kind).is_equal_to(DevToolsKind::Event);
+        assert_that!(*id).is_none();
+
+        assert_that!(log.records[9].level).is_equal_to(LogLevel::Warning);
+    }
+
+    #[test]
+    fn parses_readable_timestamps() {
+        let log = ChromeDriverLog::parse(
+            "[10-11-2025 16:03:27.123456][SEVERE]: Timed out receiving message from renderer: 10.000\n",
+        );
+
+        assert_that!(log.records.len()).is_equal_to(1);
+        assert_that!(log.records[0].timestamp).is_equal_to(LogTimestamp::Readable(
+            datetime!(2025-10-11 16:03:27.123456),
+        ));
+        assert_that!(log.records[0].level).is_equal_to(LogLevel::Severe);
+    }
+
+    #[test]
+    fn pairs_commands_with_responses() {
+        let log = ChromeDriverLog::parse(LOG);
+
+        let exchanges = log.exchanges();
+
+        let summary = exchanges
+            .iter()
+            .map(|exchange| (exchange.command.line, exchange.response.map(|it| it.line)))
+            .collect::<Vec<_>>();
+        assert_that!(summary).is_equal_to(vec![
+            (3, Some(19)),
+            (22, Some(28)),
+            (26, None),
+            (30, None),
+        ]);
+
+        let failed = log.failed_exchanges().collect::<Vec<_>>();
+        assert_that!(failed.len()).is_equal_to(1);
+        assert_that!(failed[0].error().unwrap()).is_equal_to(
+            "no such element: Unable to locate element: {\"method\":\"css selector\",\"selector\":\"#submit\"}\n  (Session info: chrome=141.0.7390.76)",
+        );
+    }
+
+    #[test]
+    fn filters_records_by_level() {
+        let log = ChromeDriverLog::parse(LOG);
+
+        let lines = |level| {
+            log.filtered(level)
+                .map(|record| record.line)
+                .collect::<Vec<_>>()
+        };
+
+        assert_that!(lines(LogLevel::Warning)).is_equal_to(vec![30]);
+        assert_that!(lines(LogLevel::Info).len()).is_equal_to(7);
+        assert_that!(lines(LogLevel::All).len()).is_equal_to(10);
+        assert_that!(lines(LogLevel::Off)).is_empty();
+    }
+
+    #[test]
+    fn reads_log_from_reader() {
+        let log = ChromeDriverLog::read(LOG.as_bytes()).unwrap();
+
+        assert_that!(log).is_equal_to(ChromeDriverLog::parse(LOG));
+    }
+}
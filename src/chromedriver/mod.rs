@@ -0,0 +1,338 @@
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use rootcause::{Report, report};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+mod args;
+mod logs;
+mod process;
+
+pub use args::ChromeDriverArgs;
+pub use logs::ChromeDriverLog;
+pub use logs::CommandExchange;
+pub use logs::DevToolsKind;
+pub use logs::LogEntry;
+pub use logs::LogRecord;
+pub use logs::LogTimestamp;
+pub use process::ChromeDriverLauncher;
+pub use process::ChromeDriverProcess;
+
+/// Error returned when parsing a log level string fails.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid log level: '{value}'.")]
+pub struct ParseLogLevelError {
+    value: String,
+}
+
+/// The chromedriver binary can be passed one of these log-levels.
+///
+/// Defaults to `Info`. This is enough to get the "started on port ..." line on stdout.
+///
+/// Levels are ordered by verbosity: `Off < Severe < Warning < Info < Debug < All`.
+///
+/// Pass it to [`ChromeDriverArgs::log_level`]:
+/// ```no_run
+/// use chrome_for_testing::chromedriver::{ChromeDriverArgs, LogLevel};
+///
+/// let command = ChromeDriverArgs::new()
+///     .log_level(LogLevel::All)
+///     .command("chromedriver");
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LogLevel {
+    /// Log all messages (most verbose).
+    All,
+
+    /// Log debug messages and above.
+    Debug,
+
+    /// Log info messages and above (default level).
+    #[default]
+    Info,
+
+    /// Log warning messages and above.
+    Warning,
+
+    /// Log only severe/error messages.
+    Severe,
+
+    /// Disable all logging.
+    Off,
+}
+
+impl Display for LogLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LogLevel::All => "ALL",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warning => "WARNING",
+            LogLevel::Severe => "SEVERE",
+            LogLevel::Off => "OFF",
+        })
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = Report<ParseLogLevelError>;
+
+    /// Parses a `ChromeDriver` level name, ignoring case. The `log` crate names "trace", "warn"
+    /// and "error" are accepted as well, so that e.g. `RUST_LOG=debug` can be used directly.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "all" | "trace" => Ok(LogLevel::All),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warning" | "warn" => Ok(LogLevel::Warning),
+            "severe" | "error" => Ok(LogLevel::Severe),
+            "off" => Ok(LogLevel::Off),
+            _ => Err(report!(ParseLogLevelError {
+                value: s.to_owned(),
+            })),
+        }
+    }
+}
+
+impl PartialOrd for LogLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LogLevel {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.verbosity().cmp(&other.verbosity())
+    }
+}
+
+impl LogLevel {
+    /// Reads the log level from the environment variable `env_var`, e.g.
+    /// "`CHROMEDRIVER_LOG_LEVEL`".
+    ///
+    /// Returns `None` if the variable is unset or empty. Values like `RUST_LOG=warn` work as
+    /// well, whereas per-module directives like `RUST_LOG=warn,my_crate=debug` do not.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLogLevelOverride`] if the variable holds an unknown level.
+    pub fn from_env(env_var: &str) -> crate::Result<Option<Self>> {
+        from_env_value(env_var, std::env::var_os(env_var))
+    }
+
+    /// Tells whether `ChromeDriver` started with this level logs messages of `level`.
+    #[must_use]
+    pub fn includes(self, level: LogLevel) -> bool {
+        self != LogLevel::Off && level != LogLevel::Off && level <= self
+    }
+
+    fn verbosity(self) -> u8 {
+        match self {
+            LogLevel::Off => 0,
+            LogLevel::Severe => 1,
+            LogLevel::Warning => 2,
+            LogLevel::Info => 3,
+            LogLevel::Debug => 4,
+            LogLevel::All => 5,
+        }
+    }
+}
+
+fn from_env_value(env_var: &str, value: Option<OsString>) -> crate::Result<Option<LogLevel>> {
+    let Some(value) = value.filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let value = value.to_string_lossy();
+    let level = value
+        .parse::<LogLevel>()
+        .context(Error::InvalidLogLevelOverride {
+            env_var: env_var.to_owned(),
+            value: value.clone().into_owned(),
+        })?;
+    Ok(Some(level))
+}
+
+#[cfg(feature = "log")]
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => LogLevel::Severe,
+            log::Level::Warn => LogLevel::Warning,
+            log::Level::Info => LogLevel::Info,
+            log::Level::Debug => LogLevel::Debug,
+            log::Level::Trace => LogLevel::All,
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<log::LevelFilter> for LogLevel {
+    fn from(filter: log::LevelFilter) -> Self {
+        filter.to_level().map_or(LogLevel::Off, LogLevel::from)
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<LogLevel> for log::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => log::LevelFilter::Off,
+            LogLevel::Severe => log::LevelFilter::Error,
+            LogLevel::Warning => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::All => log::LevelFilter::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::ERROR => LogLevel::Severe,
+            tracing::Level::WARN => LogLevel::Warning,
+            tracing::Level::INFO => LogLevel::Info,
+            tracing::Level::DEBUG => LogLevel::Debug,
+            tracing::Level::TRACE => LogLevel::All,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl From<tracing::level_filters::LevelFilter> for LogLevel {
+    fn from(filter: tracing::level_filters::LevelFilter) -> Self {
+        filter.into_level().map_or(LogLevel::Off, LogLevel::from)
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl From<LogLevel> for tracing::level_filters::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => tracing::level_filters::LevelFilter::OFF,
+            LogLevel::Severe => tracing::level_filters::LevelFilter::ERROR,
+            LogLevel::Warning => tracing::level_filters::LevelFilter::WARN,
+            LogLevel::Info => tracing::level_filters::LevelFilter::INFO,
+            LogLevel::Debug => tracing::level_filters::LevelFilter::DEBUG,
+            LogLevel::All => tracing::level_filters::LevelFilter::TRACE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn parses_level_names_ignoring_case() {
+        let cases = [
+            ("ALL", LogLevel::All),
+            ("trace", LogLevel::All),
+            ("debug", LogLevel::Debug),
+            ("Info", LogLevel::Info),
+            ("WARNING", LogLevel::Warning),
+            ("warn", LogLevel::Warning),
+            ("SEVERE", LogLevel::Severe),
+            ("error", LogLevel::Severe),
+            ("off", LogLevel::Off),
+        ];
+        for (name, level) in cases {
+            assert_that!(name.parse::<LogLevel>().unwrap()).is_equal_to(level);
+        }
+        for level in [
+            LogLevel::All,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warning,
+            LogLevel::Severe,
+            LogLevel::Off,
+        ] {
+            assert_that!(level.to_string().parse::<LogLevel>().unwrap()).is_equal_to(level);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_level_names() {
+        let err = "verbose".parse::<LogLevel>().unwrap_err();
+
+        assert_that!(err.current_context().to_string())
+            .is_equal_to("Invalid log level: 'verbose'.");
+    }
+
+    #[test]
+    fn orders_by_verbosity() {
+        let mut levels = vec![
+            LogLevel::Info,
+            LogLevel::All,
+            LogLevel::Off,
+            LogLevel::Warning,
+            LogLevel::Debug,
+            LogLevel::Severe,
+        ];
+        levels.sort();
+
+        assert_that!(levels).is_equal_to(vec![
+            LogLevel::Off,
+            LogLevel::Severe,
+            LogLevel::Warning,
+            LogLevel::Info,
+            LogLevel::Debug,
+            LogLevel::All,
+        ]);
+    }
+
+    #[test]
+    fn includes_less_verbose_levels() {
+        assert_that!(LogLevel::Info.includes(LogLevel::Severe)).is_true();
+        assert_that!(LogLevel::Info.includes(LogLevel::Info)).is_true();
+        assert_that!(LogLevel::Info.includes(LogLevel::Debug)).is_false();
+        assert_that!(LogLevel::All.includes(LogLevel::Debug)).is_true();
+        assert_that!(LogLevel::Off.includes(LogLevel::Severe)).is_false();
+        assert_that!(LogLevel::All.includes(LogLevel::Off)).is_false();
+    }
+
+    #[test]
+    fn reads_level_from_env_value() {
+        let var = "CHROMEDRIVER_LOG_LEVEL";
+
+        assert_that!(from_env_value(var, Some(OsString::from("debug"))).unwrap())
+            .is_equal_to(Some(LogLevel::Debug));
+        assert_that!(from_env_value(var, Some(OsString::new())).unwrap()).is_none();
+        assert_that!(from_env_value(var, None).unwrap()).is_none();
+
+        let err = from_env_value(var, Some(OsString::from("loud"))).unwrap_err();
+        let Error::InvalidLogLevelOverride { env_var, value } = err.current_context() else {
+            panic!(
+                "expected invalid log level error, got: {:?}",
+                err.current_context()
+            );
+        };
+        assert_that!(env_var.as_str()).is_equal_to(var);
+        assert_that!(value.as_str()).is_equal_to("loud");
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn converts_from_and_to_log_levels() {
+        assert_that!(LogLevel::from(log::Level::Trace)).is_equal_to(LogLevel::All);
+        assert_that!(LogLevel::from(log::Level::Error)).is_equal_to(LogLevel::Severe);
+        assert_that!(LogLevel::from(log::LevelFilter::Off)).is_equal_to(LogLevel::Off);
+        assert_that!(log::LevelFilter::from(LogLevel::Warning)).is_equal_to(log::LevelFilter::Warn);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn converts_from_and_to_tracing_levels() {
+        use tracing::level_filters::LevelFilter;
+
+        assert_that!(LogLevel::from(tracing::Level::TRACE)).is_equal_to(LogLevel::All);
+        assert_that!(LogLevel::from(tracing::Level::WARN)).is_equal_to(LogLevel::Warning);
+        assert_that!(LogLevel::from(LevelFilter::OFF)).is_equal_to(LogLevel::Off);
+        assert_that!(LevelFilter::from(LogLevel::Debug)).is_equal_to(LevelFilter::DEBUG);
+    }
+}
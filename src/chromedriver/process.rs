@@ -0,0 +1,534 @@
+use crate::chromedriver::{ChromeDriverArgs, LogLevel};
+use crate::diagnostics::{Diagnostics, OutputBuffer};
+use crate::error::Error;
+use crate::process::{capture_output, collect_diagnostics, startup_failure, terminate};
+use rootcause::prelude::ResultExt;
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Part of the line `ChromeDriver` prints to stdout once it accepts connections, e.g.
+/// "`ChromeDriver` was started successfully on port 9515."
+const READY_MARKER: &str = "started successfully";
+
+/// Default time to wait for `ChromeDriver` to report a successful start.
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the process is checked for an exit once its output is closed.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Configures and spawns a [`ChromeDriverProcess`].
+///
+/// ```no_run
+/// use chrome_for_testing::chromedriver::{ChromeDriverArgs, ChromeDriverLauncher, LogLevel};
+///
+/// # fn main() -> chrome_for_testing::Result<()> {
+/// let chromedriver = ChromeDriverLauncher::new("/opt/chromedriver-linux64/chromedriver")
+///     .args(ChromeDriverArgs::new().log_level(LogLevel::Warning))
+///     .spawn()?;
+/// println!("WebDriver available at {}", chromedriver.url());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChromeDriverLauncher {
+    executable: PathBuf,
+    args: ChromeDriverArgs,
+    startup_timeout: Duration,
+}
+
+impl ChromeDriverLauncher {
+    /// Creates a launcher for the `ChromeDriver` executable at `executable`.
+    ///
+    /// By default, `ChromeDriver` listens on a port allocated by the operating system and is
+    /// given 30 seconds to start.
+    #[must_use]
+    pub fn new(executable: impl Into<PathBuf>) -> Self {
+        Self {
+            executable: executable.into(),
+            args: ChromeDriverArgs::new(),
+            startup_timeout: DEFAULT_STARTUP_TIMEOUT,
+        }
+    }
+
+    /// Sets the arguments to start `ChromeDriver` with, replacing those configured before.
+    ///
+    /// Without [`ChromeDriverArgs::port`], `--port=0` is passed, letting the operating system
+    /// allocate a free port. Do not pass [`ChromeDriverArgs::silent`], which suppresses the line
+    /// announcing a successful start.
+    #[must_use]
+    pub fn args(mut self, args: ChromeDriverArgs) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Sets the port to listen on. Use `0` to let the operating system allocate a free port.
+    ///
+    /// Shorthand for [`ChromeDriverArgs::port`].
+    #[must_use]
+    pub fn port(mut self, port: u16) -> Self {
+        self.args = self.args.port(port);
+        self
+    }
+
+    /// Sets the log level.
+    ///
+    /// Shorthand for [`ChromeDriverArgs::log_level`].
+    #[must_use]
+    pub fn log_level(mut self, log_level: LogLevel) -> Self {
+        self.args = self.args.log_level(log_level);
+        self
+    }
+
+    /// Writes the `ChromeDriver` log to the file at `path` instead of stderr.
+    ///
+    /// Shorthand for [`ChromeDriverArgs::log_path`].
+    #[must_use]
+    pub fn log_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.args = self.args.log_path(path);
+        self
+    }
+
+    /// Allows remote connections from the given IP address. By default, only local connections
+    /// are allowed.
+    ///
+    /// Shorthand for [`ChromeDriverArgs::allowed_ip`].
+    #[must_use]
+    pub fn allowed_ip(mut self, ip: impl Into<String>) -> Self {
+        self.args = self.args.allowed_ip(ip);
+        self
+    }
+
+    /// Allows requests from the given origin, e.g. "`http://localhost:3000`".
+    ///
+    /// Shorthand for [`ChromeDriverArgs::allowed_origin`].
+    #[must_use]
+    pub fn allowed_origin(mut self, origin: impl Into<String>) -> Self {
+        self.args = self.args.allowed_origin(origin);
+        self
+    }
+
+    /// Sets how long to wait for `ChromeDriver` to report a successful start.
+    #[must_use]
+    pub fn startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    fn command(&self) -> Command {
+        match self.args.configured_port() {
+            Some(_) => self.args.command(&self.executable),
+            None => self.args.clone().port(0).command(&self.executable),
+        }
+    }
+
+    /// Spawns `ChromeDriver` and waits until it reports a successful start.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the executable cannot be spawned, or if it exits or does not report a
+    /// successful start within the startup timeout. The error carries [`Diagnostics`] about
+    /// `ChromeDriver`'s output.
+    pub fn spawn(&self) -> crate::Result<ChromeDriverProcess> {
+        let mut child = self
+            .command()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context_to::<Error>()
+            .attach_with(|| {
+                format!(
+                    "while spawning ChromeDriver: '{}'",
+                    self.executable.display()
+                )
+            })?;
+
+        let output = Arc::new(Mutex::new(OutputBuffer::default()));
+        let receiver = capture_output(&mut child, &output);
+
+        let deadline = Instant::now() + self.startup_timeout;
+        let mut announced_port = None;
+        let port = loop {
+            let line = match receiver
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            {
+                Ok(line) => line,
+                Err(err) => {
+                    let exit_status = match err {
+                        mpsc::RecvTimeoutError::Timeout => None,
+                        mpsc::RecvTimeoutError::Disconnected => wait_for_exit(&mut child, deadline),
+                    };
+                    let reason = match exit_status {
+                        Some(status) => {
+                            format!("process exited before reporting a successful start ({status})")
+                        }
+                        None => format!(
+                            "no successful start reported within {:?}",
+                            self.startup_timeout
+                        ),
+                    };
+                    return Err(startup_failure(
+                        &mut child,
+                        "ChromeDriver",
+                        &output,
+                        exit_status,
+                        Error::ChromeDriverStartup { reason },
+                    ));
+                }
+            };
+
+            // With `--port=0`, the "Starting ChromeDriver" line announces port 0.
+            if let Some(port) = parse_port(&line).filter(|port| *port != 0) {
+                announced_port = Some(port);
+            }
+            if line.contains(READY_MARKER) {
+                let configured_port = self.args.configured_port().filter(|port| *port != 0);
+                match announced_port.or(configured_port) {
+                    Some(port) => break port,
+                    None => {
+                        return Err(startup_failure(
+                            &mut child,
+                            "ChromeDriver",
+                            &output,
+                            None,
+                            Error::ChromeDriverStartup {
+                                reason: String::from("could not determine the port it listens on"),
+                            },
+                        ));
+                    }
+                }
+            }
+        };
+
+        let url_base = self
+            .args
+            .configured_url_base()
+            .map(|base| base.trim_matches('/'))
+            .filter(|base| !base.is_empty())
+            .map(|base| format!("{base}/"))
+            .unwrap_or_default();
+        let url = Url::parse(&format!("http://127.0.0.1:{port}/{url_base}")).expect("Valid URL");
+
+        Ok(ChromeDriverProcess {
+            child,
+            port,
+            url,
+            output,
+        })
+    }
+}
+
+/// A running `ChromeDriver`, killed when dropped.
+#[derive(Debug)]
+pub struct ChromeDriverProcess {
+    child: Child,
+    port: u16,
+    url: Url,
+    output: Arc<Mutex<OutputBuffer>>,
+}
+
+impl ChromeDriverProcess {
+    /// The port `ChromeDriver` listens on.
+    #[must_use]
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// The WebDriver URL to connect to, e.g. "`http://127.0.0.1:9515/`", including the
+    /// configured URL base.
+    #[must_use]
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// The OS process ID.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Diagnostics about `ChromeDriver`'s latest output, e.g. to explain a failed WebDriver
+    /// session. Pass [`ChromeDriverArgs::enable_chrome_logs`] to include Chrome's output.
+    #[must_use]
+    pub fn diagnostics(&mut self) -> Diagnostics {
+        let exit_status = self.child.try_wait().ok().flatten();
+        collect_diagnostics("ChromeDriver", &self.output, exit_status)
+    }
+
+    /// Kills the process and waits for it to exit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process could not be killed or waited for.
+    pub fn kill(mut self) -> crate::Result<()> {
+        terminate(&mut self.child, "ChromeDriver")
+    }
+}
+
+impl Drop for ChromeDriverProcess {
+    fn drop(&mut self) {
+        let _ = terminate(&mut self.child, "ChromeDriver");
+    }
+}
+
+/// Extracts the port from lines like "Starting `ChromeDriver` 120.0.6099.109 (...) on port 9515"
+/// or "`ChromeDriver` was started successfully on port 9515.".
+fn parse_port(line: &str) -> Option<u16> {
+    let (_, rest) = line.rsplit_once("on port ")?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(rest, |end| &rest[..end]);
+    digits.parse().ok()
+}
+
+/// Waits until `child`, which closed its output, exits or `deadline` passes. Closed pipes
+/// usually mean an exit, but the process may keep running without them.
+fn wait_for_exit(child: &mut Child, deadline: Instant) -> Option<ExitStatus> {
+    loop {
+        if let Ok(Some(status)) = child.try_wait() {
+            return Some(status);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        std::thread::sleep(POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn parses_port_from_startup_lines() {
+        assert_that!(parse_port(
+            "Starting ChromeDriver 120.0.6099.109 (3419140ab665596f21b385ce136419fde0924272-refs/branch-heads/6099@{#1483}) on port 9515"
+        ))
+        .is_equal_to(Some(9515));
+        assert_that!(parse_port(
+            "ChromeDriver was started successfully on port 41873."
+        ))
+        .is_equal_to(Some(41873));
+        assert_that!(parse_port("ChromeDriver was started successfully.")).is_none();
+    }
+
+    #[test]
+    fn command_contains_configured_arguments() {
+        let launcher = ChromeDriverLauncher::new("chromedriver")
+            .port(4444)
+            .log_level(LogLevel::Debug)
+            .log_file("/tmp/chromedriver.log")
+            .allowed_ip("10.0.0.1")
+            .allowed_ip("10.0.0.2")
+            .allowed_origin("http://localhost:3000");
+
+        let command = launcher.command();
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+
+        assert_that!(args).is_equal_to(vec![
+            String::from("--port=4444"),
+            String::from("--log-path=/tmp/chromedriver.log"),
+            String::from("--log-level=DEBUG"),
+            String::from("--allowed-ips=10.0.0.1,10.0.0.2"),
+            String::from("--allowed-origins=http://localhost:3000"),
+        ]);
+    }
+
+    #[test]
+    fn command_allocates_port_unless_configured() {
+        let args_of = |launcher: ChromeDriverLauncher| {
+            launcher
+                .command()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert_that!(args_of(ChromeDriverLauncher::new("chromedriver")))
+            .is_equal_to(vec![String::from("--port=0")]);
+        assert_that!(args_of(
+            ChromeDriverLauncher::new("chromedriver").args(
+                ChromeDriverArgs::new()
+                    .port(4444)
+                    .log_level(LogLevel::Debug)
+            )
+        ))
+        .is_equal_to(vec![
+            String::from("--port=4444"),
+            String::from("--log-level=DEBUG"),
+        ]);
+    }
+
+    #[cfg(unix)]
+    mod fake_chromedriver {
+        use super::*;
+        use std::os::unix::fs::PermissionsExt;
+
+        fn fake_chromedriver(dir: &tempfile::TempDir, script: &str) -> PathBuf {
+            let path = dir.path().join("chromedriver");
+            std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        }
+
+        #[test]
+        fn discovers_auto_allocated_port() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = fake_chromedriver(
+                &dir,
+                r#"echo "Starting ChromeDriver 120.0.6099.109 on port 0"
+echo "Only local connections are allowed."
+echo "ChromeDriver was started successfully on port 43215."
+exec sleep 30"#,
+            );
+
+            let process = ChromeDriverLauncher::new(executable).spawn().unwrap();
+
+            assert_that!(process.port()).is_equal_to(43215);
+            assert_that!(process.url().as_str()).is_equal_to("http://127.0.0.1:43215/");
+        }
+
+        #[test]
+        fn falls_back_to_configured_port_for_banners_without_port() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = fake_chromedriver(
+                &dir,
+                r#"echo "ChromeDriver was started successfully."
+exec sleep 30"#,
+            );
+
+            let process = ChromeDriverLauncher::new(executable)
+                .args(ChromeDriverArgs::new().port(9515).url_base("/wd/hub"))
+                .spawn()
+                .unwrap();
+
+            assert_that!(process.port()).is_equal_to(9515);
+            assert_that!(process.url().as_str()).is_equal_to("http://127.0.0.1:9515/wd/hub/");
+        }
+
+        #[test]
+        fn reports_early_exit_with_output() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = fake_chromedriver(
+                &dir,
+                r#"echo "bind() failed: Address already in use (98)" >&2
+exit 1"#,
+            );
+
+            let err = ChromeDriverLauncher::new(executable).spawn().unwrap_err();
+
+            let Error::ChromeDriverStartup { reason } = err.current_context() else {
+                panic!(
+                    "expected ChromeDriver startup error, got: {:?}",
+                    err.current_context()
+                );
+            };
+
+            assert_that!(reason.as_str())
+                .starts_with("process exited before reporting a successful start");
+            let diagnostics = Diagnostics::from_report(&err).unwrap();
+            assert_that!(diagnostics.output.len()).is_equal_to(1);
+            assert_that!(diagnostics.output[0].text.as_str())
+                .is_equal_to("bind() failed: Address already in use (98)");
+            assert_that!(format!("{err:?}")).contains("Address already in use");
+        }
+
+        #[test]
+        fn reports_startup_timeout() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = fake_chromedriver(&dir, "exec sleep 30");
+
+            let err = ChromeDriverLauncher::new(executable)
+                .startup_timeout(Duration::from_millis(200))
+                .spawn()
+                .unwrap_err();
+
+            let Error::ChromeDriverStartup { reason } = err.current_context() else {
+                panic!(
+                    "expected ChromeDriver startup error, got: {:?}",
+                    err.current_context()
+                );
+            };
+
+            assert_that!(reason.as_str()).starts_with("no successful start reported");
+        }
+
+        #[test]
+        fn reports_startup_timeout_after_output_is_closed() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = fake_chromedriver(&dir, "exec sleep 30 >&- 2>&-");
+
+            let started = Instant::now();
+            let err = ChromeDriverLauncher::new(executable)
+                .startup_timeout(Duration::from_millis(200))
+                .spawn()
+                .unwrap_err();
+
+            let Error::ChromeDriverStartup { reason } = err.current_context() else {
+                panic!(
+                    "expected ChromeDriver startup error, got: {:?}",
+                    err.current_context()
+                );
+            };
+
+            assert_that!(reason.as_str()).starts_with("no successful start reported");
+            assert_that!(started.elapsed() < Duration::from_secs(10)).is_true();
+        }
+
+        #[test]
+        fn keeps_capturing_output_after_start() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = fake_chromedriver(
+                &dir,
+                r#"echo "ChromeDriver was started successfully on port 9515."
+echo "[1760191407.489][SEVERE]: session not created: DevToolsActivePort file doesn't exist" >&2
+exec sleep 30"#,
+            );
+
+            let mut process = ChromeDriverLauncher::new(executable).spawn().unwrap();
+
+            let deadline = Instant::now() + Duration::from_secs(5);
+            let diagnostics = loop {
+                let diagnostics = process.diagnostics();
+                if !diagnostics.findings.is_empty() || Instant::now() > deadline {
+                    break diagnostics;
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            };
+
+            assert_that!(diagnostics.exit_status).is_none();
+            assert_that!(diagnostics.findings[0].issue.clone())
+                .is_equal_to(crate::diagnostics::KnownIssue::DevToolsActivePortMissing);
+        }
+
+        #[test]
+        fn kills_process_on_drop() {
+            let dir = tempfile::tempdir().unwrap();
+            let executable = fake_chromedriver(
+                &dir,
+                r#"echo "ChromeDriver was started successfully on port 9515."
+exec sleep 30"#,
+            );
+
+            let process = ChromeDriverLauncher::new(executable).spawn().unwrap();
+            let pid = process.id();
+            drop(process);
+
+            let alive = Command::new("kill")
+                .args(["-0", &pid.to_string()])
+                .stderr(Stdio::null())
+                .status()
+                .unwrap()
+                .success();
+            assert_that!(alive).is_false();
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use std::path::Path;
+
+/// Network configuration for HTTP clients constructed by this crate.
+///
+/// Use this when you need the crate to talk through an HTTP(S) proxy, to trust additional root
+/// certificates (e.g. of a corporate MITM proxy), or to authenticate with a client certificate.
+///
+/// ```no_run
+/// use chrome_for_testing::{ClientConfig, KnownGoodVersions};
+///
+/// # async fn example() -> chrome_for_testing::Result<()> {
+/// let client = ClientConfig::from_env()?
+///     .root_certificate_file("/etc/ssl/certs/corporate-ca.pem")?
+///     .build()?;
+/// let versions = KnownGoodVersions::fetch(&client).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientConfig {
+    all_proxy: Option<String>,
+    http_proxy: Option<String>,
+    https_proxy: Option<String>,
+    no_proxy: Option<String>,
+    root_certificates_pem: Vec<Vec<u8>>,
+    identity_pem: Option<Vec<u8>>,
+}
+
+impl ClientConfig {
+    /// Creates an empty configuration.
+    ///
+    /// Clients built from it behave like `reqwest` defaults, including the automatic use of the
+    /// system proxy configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a configuration from the standard environment variables.
+    ///
+    /// Reads `ALL_PROXY`, `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` as well as `SSL_CERT_FILE`,
+    /// whose certificates are trusted in addition to the built-in roots. Like curl, the lowercase
+    /// proxy variants take precedence. `HTTP_PROXY` is ignored when `REQUEST_METHOD` is set, as in
+    /// CGI environments it can be injected through the `Proxy` request header ("httpoxy").
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `SSL_CERT_FILE` is set but cannot be read.
+    pub fn from_env() -> crate::Result<Self> {
+        Self::from_env_with(|name| std::env::var(name).ok())
+    }
+
+    fn from_env_with(var: impl Fn(&str) -> Option<String>) -> crate::Result<Self> {
+        let non_empty = |name: &str| var(name).filter(|value| !value.is_empty());
+        let lookup = |name: &str| non_empty(&name.to_ascii_lowercase()).or_else(|| non_empty(name));
+        let is_cgi = var("REQUEST_METHOD").is_some();
+
+        let mut config = Self {
+            all_proxy: lookup("ALL_PROXY"),
+            http_proxy: if is_cgi {
+                non_empty("http_proxy")
+            } else {
+                lookup("HTTP_PROXY")
+            },
+            https_proxy: lookup("HTTPS_PROXY"),
+            no_proxy: lookup("NO_PROXY"),
+            ..Self::default()
+        };
+        if let Some(path) = var("SSL_CERT_FILE").filter(|value| !value.is_empty()) {
+            config = config.root_certificate_file(path)?;
+        }
+        Ok(config)
+    }
+
+    /// Routes all requests through the given proxy URL.
+    #[must_use]
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.all_proxy = Some(url.into());
+        self
+    }
+
+    /// Routes plain HTTP requests through the given proxy URL.
+    #[must_use]
+    pub fn http_proxy(mut self, url: impl Into<String>) -> Self {
+        self.http_proxy = Some(url.into());
+        self
+    }
+
+    /// Routes HTTPS requests through the given proxy URL.
+    #[must_use]
+    pub fn https_proxy(mut self, url: impl Into<String>) -> Self {
+        self.https_proxy = Some(url.into());
+        self
+    }
+
+    /// Excludes hosts from proxying, using the comma-separated `NO_PROXY` syntax.
+    ///
+    /// Only applies to the proxies configured on this `ClientConfig`. Without any, clients use the
+    /// system proxy configuration, which is subject to the `NO_PROXY` environment variable
+    /// instead.
+    #[must_use]
+    pub fn no_proxy(mut self, hosts: impl Into<String>) -> Self {
+        self.no_proxy = Some(hosts.into());
+        self
+    }
+
+    /// Trusts the PEM-encoded certificates in `pem` in addition to the built-in roots.
+    #[must_use]
+    pub fn root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates_pem.push(pem.into());
+        self
+    }
+
+    /// Trusts the PEM-encoded certificates in the file at `path` in addition to the built-in
+    /// roots.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn root_certificate_file(self, path: impl AsRef<Path>) -> crate::Result<Self> {
+        let path = path.as_ref();
+        let pem = std::fs::read(path)
+            .context_to::<Error>()
+            .attach_with(|| format!("while reading root certificates: '{}'", path.display()))?;
+        Ok(self.root_certificate_pem(pem))
+    }
+
+    /// Authenticates with the given client identity: a PEM bundle containing a private key and
+    /// its certificate chain.
+    #[must_use]
+    pub fn identity_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.identity_pem = Some(pem.into());
+        self
+    }
+
+    /// Builds an async `reqwest` client using this configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a proxy URL, certificate or identity is invalid.
+    pub fn build(&self) -> crate::Result<reqwest::Client> {
+        let settings = self.settings()?;
+        let mut builder = reqwest::Client::builder();
+        for proxy in settings.proxies {
+            builder = builder.proxy(proxy);
+        }
+        builder = builder.tls_certs_merge(settings.root_certificates);
+        if let Some(identity) = settings.identity {
+            builder = builder.identity(identity);
+        }
+        builder
+            .build()
+            .context_to::<Error>()
+            .attach("while building HTTP client")
+    }
+
+    /// Builds a blocking `reqwest` client using this configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a proxy URL, certificate or identity is invalid.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(&self) -> crate::Result<reqwest::blocking::Client> {
+        let settings = self.settings()?;
+        let mut builder = reqwest::blocking::Client::builder();
+        for proxy in settings.proxies {
+            builder = builder.proxy(proxy);
+        }
+        builder = builder.tls_certs_merge(settings.root_certificates);
+        if let Some(identity) = settings.identity {
+            builder = builder.identity(identity);
+        }
+        builder
+            .build()
+            .context_to::<Error>()
+            .attach("while building HTTP client")
+    }
+
+    fn settings(&self) -> crate::Result<Settings> {
+        let no_proxy = self
+            .no_proxy
+            .as_deref()
+            .and_then(reqwest::NoProxy::from_string);
+
+        let mut proxies = Vec::new();
+        let configured = [
+            (&self.http_proxy, ProxyScope::Http),
+            (&self.https_proxy, ProxyScope::Https),
+            (&self.all_proxy, ProxyScope::All),
+        ];
+        for (url, scope) in configured {
+            let Some(url) = url else {
+                continue;
+            };
+            let proxy = match scope {
+                ProxyScope::Http => reqwest::Proxy::http(url),
+                ProxyScope::Https => reqwest::Proxy::https(url),
+                ProxyScope::All => reqwest::Proxy::all(url),
+            }
+            .context_to::<Error>()
+            .attach_with(|| format!("while configuring proxy: '{url}'"))?;
+            proxies.push(proxy.no_proxy(no_proxy.clone()));
+        }
+
+        let mut root_certificates = Vec::new();
+        for pem in &self.root_certificates_pem {
+            root_certificates.extend(
+                reqwest::Certificate::from_pem_bundle(pem)
+                    .context_to::<Error>()
+                    .attach("while parsing root certificates")?,
+            );
+        }
+
+        let identity = self
+            .identity_pem
+            .as_deref()
+            .map(|pem| {
+                reqwest::Identity::from_pem(pem)
+                    .context_to::<Error>()
+                    .attach("while parsing client identity")
+            })
+            .transpose()?;
+
+        Ok(Settings {
+            proxies,
+            root_certificates,
+            identity,
+        })
+    }
+}
+
+enum ProxyScope {
+    Http,
+    Https,
+    All,
+}
+
+struct Settings {
+    proxies: Vec<reqwest::Proxy>,
+    root_certificates: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KnownGoodVersions;
+    use crate::api::endpoint::Endpoint;
+    use assertr::prelude::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn from_env_reads_standard_variables() {
+        let vars = HashMap::from([
+            ("HTTPS_PROXY", "http://proxy.internal:3128"),
+            ("http_proxy", "http://plain-proxy.internal:3128"),
+            ("NO_PROXY", "localhost,.internal"),
+            ("ALL_PROXY", ""),
+        ]);
+
+        let config =
+            ClientConfig::from_env_with(|name| vars.get(name).map(ToString::to_string)).unwrap();
+
+        assert_that!(config).is_equal_to(
+            ClientConfig::new()
+                .https_proxy("http://proxy.internal:3128")
+                .http_proxy("http://plain-proxy.internal:3128")
+                .no_proxy("localhost,.internal"),
+        );
+    }
+
+    #[test]
+    fn from_env_prefers_lowercase_variables() {
+        let vars = HashMap::from([
+            ("HTTPS_PROXY", "http://upper.internal:3128"),
+            ("https_proxy", "http://lower.internal:3128"),
+        ]);
+
+        let config =
+            ClientConfig::from_env_with(|name| vars.get(name).map(ToString::to_string)).unwrap();
+
+        assert_that!(config)
+            .is_equal_to(ClientConfig::new().https_proxy("http://lower.internal:3128"));
+    }
+
+    #[test]
+    fn from_env_ignores_uppercase_http_proxy_in_cgi_environments() {
+        let vars = HashMap::from([
+            ("REQUEST_METHOD", "GET"),
+            ("HTTP_PROXY", "http://attacker.example:3128"),
+            ("HTTPS_PROXY", "http://proxy.internal:3128"),
+        ]);
+
+        let config =
+            ClientConfig::from_env_with(|name| vars.get(name).map(ToString::to_string)).unwrap();
+
+        assert_that!(config)
+            .is_equal_to(ClientConfig::new().https_proxy("http://proxy.internal:3128"));
+    }
+
+    #[test]
+    fn from_env_reads_ssl_cert_file() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test-data/test-certificate.pem"
+        );
+
+        let config =
+            ClientConfig::from_env_with(|name| (name == "SSL_CERT_FILE").then(|| path.to_owned()))
+                .unwrap();
+
+        assert_that!(config)
+            .is_equal_to(ClientConfig::new().root_certificate_pem(
+                include_bytes!("../test-data/test-certificate.pem").to_vec(),
+            ));
+    }
+
+    #[test]
+    fn from_env_reports_unreadable_ssl_cert_file() {
+        let err = ClientConfig::from_env_with(|name| {
+            (name == "SSL_CERT_FILE").then(|| String::from("/does/not/exist.pem"))
+        })
+        .unwrap_err();
+
+        let Error::Io(io_error) = err.current_context() else {
+            panic!("expected I/O error, got: {:?}", err.current_context());
+        };
+
+        assert_that!(io_error.kind()).is_equal_to(std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn build_accepts_certificates_and_identity() {
+        let client = ClientConfig::new()
+            .root_certificate_pem(include_bytes!("../test-data/test-certificate.pem").to_vec())
+            .identity_pem(include_bytes!("../test-data/test-identity.pem").to_vec())
+            .build();
+
+        assert_that!(client).is_ok();
+    }
+
+    #[test]
+    fn build_rejects_invalid_identity() {
+        let client = ClientConfig::new()
+            .identity_pem(b"not a pem".to_vec())
+            .build();
+
+        assert_that!(client).is_err();
+    }
+
+    #[tokio::test]
+    async fn requests_are_routed_through_proxy() {
+        let mut proxy = mockito::Server::new_async().await;
+        let _mock = proxy
+            .mock("GET", KnownGoodVersions::PATH)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(include_str!(
+                "../test-data/known_good_versions_with_downloads_test_response.json"
+            ))
+            .create();
+
+        let client = ClientConfig::new().http_proxy(proxy.url()).build().unwrap();
+        let unreachable: url::Url = "http://chrome-for-testing.invalid".parse().unwrap();
+
+        let data = KnownGoodVersions::fetch_with_base_url(&client, &unreachable)
+            .await
+            .unwrap();
+
+        assert_that!(data.versions.len()).is_equal_to(2);
+    }
+}
@@ -0,0 +1,448 @@
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+mod shared_libraries;
+
+pub use shared_libraries::DEB_DEPS_FILE;
+pub use shared_libraries::DebDependency;
+pub use shared_libraries::DebPackage;
+pub use shared_libraries::MissingLibrary;
+pub use shared_libraries::SharedLibraryReport;
+pub use shared_libraries::check_shared_libraries;
+
+/// Number of lines an [`OutputBuffer`] created by the launchers keeps.
+pub const DEFAULT_OUTPUT_CAPACITY: usize = 200;
+
+/// Maximum number of bytes kept per output line. Verbose logs may contain huge lines, e.g.
+/// base64-encoded screenshots.
+const MAX_LINE_LEN: usize = 4096;
+
+/// The stream an [`OutputLine`] was written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OutputStream {
+    /// Standard output.
+    Stdout,
+
+    /// Standard error.
+    Stderr,
+}
+
+impl Display for OutputStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OutputStream::Stdout => "stdout",
+            OutputStream::Stderr => "stderr",
+        })
+    }
+}
+
+/// A line written by a child process.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OutputLine {
+    /// The stream the line was written to.
+    pub stream: OutputStream,
+
+    /// The line, without its line terminator. Overly long lines are truncated.
+    pub text: String,
+}
+
+/// A ring buffer keeping the last lines written by a child process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputBuffer {
+    lines: VecDeque<OutputLine>,
+    capacity: usize,
+    dropped: usize,
+}
+
+impl Default for OutputBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_OUTPUT_CAPACITY)
+    }
+}
+
+impl OutputBuffer {
+    /// Creates a buffer keeping the last `capacity` lines.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity.min(DEFAULT_OUTPUT_CAPACITY)),
+            capacity,
+            dropped: 0,
+        }
+    }
+
+    /// Appends a line, dropping the oldest one if the buffer is full.
+    pub fn push(&mut self, stream: OutputStream, text: &str) {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+            self.dropped += 1;
+        }
+        self.lines.push_back(OutputLine {
+            stream,
+            text: truncate(text),
+        });
+    }
+
+    /// The kept lines, oldest first.
+    pub fn lines(&self) -> impl Iterator<Item = &OutputLine> {
+        self.lines.iter()
+    }
+
+    /// The number of lines dropped because the buffer was full.
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.len() <= MAX_LINE_LEN {
+        return text.to_owned();
+    }
+    let mut end = MAX_LINE_LEN;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}... ({} bytes truncated)", &text[..end], text.len() - end)
+}
+
+/// A well-known reason for Chrome or `ChromeDriver` failing, recognized from their output.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KnownIssue {
+    /// A shared library the executable links against is not installed, typical for fresh Linux
+    /// containers.
+    MissingSharedLibrary {
+        /// The file name of the library, e.g. "libnss3.so".
+        library: String,
+    },
+
+    /// Chrome's sandbox cannot be set up, e.g. when running as root or in a container without
+    /// user namespaces.
+    SandboxUnavailable,
+
+    /// A headful Chrome found no X server to connect to.
+    NoDisplay,
+
+    /// `/dev/shm` is too small, which is the default in Docker containers.
+    SharedMemoryExhausted,
+
+    /// Chrome started, but did not expose its `DevTools` endpoint to `ChromeDriver`. Usually a
+    /// consequence of Chrome crashing during startup.
+    DevToolsActivePortMissing,
+
+    /// Chrome crashed while `ChromeDriver` started it.
+    BrowserCrashed,
+
+    /// `ChromeDriver` and Chrome have different major versions.
+    VersionMismatch,
+}
+
+impl KnownIssue {
+    /// Recognizes the issue reported by a single line of output, if any.
+    #[must_use]
+    pub fn detect(line: &str) -> Option<Self> {
+        if let Some((_, rest)) = line.split_once("error while loading shared libraries: ") {
+            let library = rest.split(':').next().unwrap_or(rest).trim();
+            return Some(KnownIssue::MissingSharedLibrary {
+                library: library.to_owned(),
+            });
+        }
+        let patterns: [(&[&str], KnownIssue); 6] = [
+            (
+                &[
+                    "No usable sandbox",
+                    "Running as root without --no-sandbox is not supported",
+                    "The SUID sandbox helper binary was found, but is not configured correctly",
+                    "Failed to move to new namespace",
+                ],
+                KnownIssue::SandboxUnavailable,
+            ),
+            (&["Missing X server or $DISPLAY"], KnownIssue::NoDisplay),
+            (
+                &["Creating shared memory in /dev/shm"],
+                KnownIssue::SharedMemoryExhausted,
+            ),
+            (
+                &["DevToolsActivePort file doesn't exist"],
+                KnownIssue::DevToolsActivePortMissing,
+            ),
+            (
+                &["Chrome failed to start: crashed", "exited abnormally"],
+                KnownIssue::BrowserCrashed,
+            ),
+            (
+                &["This version of ChromeDriver only supports Chrome version"],
+                KnownIssue::VersionMismatch,
+            ),
+        ];
+        patterns
+            .into_iter()
+            .find(|(needles, _)| needles.iter().any(|needle| line.contains(needle)))
+            .map(|(_, issue)| issue)
+    }
+
+    /// A suggestion on how to resolve the issue.
+    #[must_use]
+    pub fn hint(&self) -> &'static str {
+        match self {
+            KnownIssue::MissingSharedLibrary { .. } => {
+                "install Chrome's system dependencies, e.g. the packages listed in the 'deb.deps' file of the download; see `check_shared_libraries()`"
+            }
+            KnownIssue::SandboxUnavailable => {
+                "run as a non-root user, enable user namespaces, or pass --no-sandbox in isolated environments"
+            }
+            KnownIssue::NoDisplay => "run headless, or start a virtual display like Xvfb",
+            KnownIssue::SharedMemoryExhausted => {
+                "pass --disable-dev-shm-usage, or enlarge /dev/shm (e.g. `docker run --shm-size=2g`)"
+            }
+            KnownIssue::DevToolsActivePortMissing | KnownIssue::BrowserCrashed => {
+                "Chrome crashed during startup; check its output for the underlying issue"
+            }
+            KnownIssue::VersionMismatch => {
+                "use the ChromeDriver matching the installed Chrome version"
+            }
+        }
+    }
+}
+
+impl Display for KnownIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KnownIssue::MissingSharedLibrary { library } => {
+                write!(f, "Missing shared library '{library}'")?;
+            }
+            KnownIssue::SandboxUnavailable => f.write_str("Sandbox unavailable")?,
+            KnownIssue::NoDisplay => f.write_str("No display")?,
+            KnownIssue::SharedMemoryExhausted => f.write_str("Shared memory exhausted")?,
+            KnownIssue::DevToolsActivePortMissing => f.write_str("DevToolsActivePort missing")?,
+            KnownIssue::BrowserCrashed => f.write_str("Browser crashed")?,
+            KnownIssue::VersionMismatch => f.write_str("Version mismatch")?,
+        }
+        write!(f, ": {}", self.hint())
+    }
+}
+
+/// A [`KnownIssue`] together with the line it was recognized in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Finding {
+    /// The recognized issue.
+    pub issue: KnownIssue,
+
+    /// The line of output reporting the issue.
+    pub line: String,
+}
+
+/// What is known about a failed Chrome or `ChromeDriver` process: recognized issues and its
+/// last lines of output.
+///
+/// The launchers attach diagnostics to startup errors. Retrieve them with
+/// [`Diagnostics::from_report`]:
+///
+/// ```no_run
+/// use chrome_for_testing::chromedriver::ChromeDriverLauncher;
+/// use chrome_for_testing::diagnostics::Diagnostics;
+///
+/// if let Err(err) = ChromeDriverLauncher::new("chromedriver").spawn() {
+///     if let Some(diagnostics) = Diagnostics::from_report(&err) {
+///         for finding in &diagnostics.findings {
+///             eprintln!("{}", finding.issue);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// The name of the process, e.g. "Chrome".
+    pub process: String,
+
+    /// The exit status, if the process exited.
+    pub exit_status: Option<String>,
+
+    /// The issues recognized in the output, each reported once.
+    pub findings: Vec<Finding>,
+
+    /// The last lines of output.
+    pub output: Vec<OutputLine>,
+
+    /// The number of earlier lines not kept.
+    pub dropped_lines: usize,
+}
+
+impl Diagnostics {
+    /// Collects diagnostics from the output of a process.
+    #[must_use]
+    pub fn from_output(process: impl Into<String>, output: &OutputBuffer) -> Self {
+        let mut findings = Vec::<Finding>::new();
+        for line in output.lines() {
+            if let Some(issue) = KnownIssue::detect(&line.text)
+                && !findings.iter().any(|finding| finding.issue == issue)
+            {
+                findings.push(Finding {
+                    issue,
+                    line: line.text.clone(),
+                });
+            }
+        }
+        Self {
+            process: process.into(),
+            exit_status: None,
+            findings,
+            output: output.lines().cloned().collect(),
+            dropped_lines: output.dropped(),
+        }
+    }
+
+    /// Finds the diagnostics attached to `report` or one of its causes.
+    #[must_use]
+    pub fn from_report(report: &rootcause::Report<Error>) -> Option<&Diagnostics> {
+        report.iter_reports().find_map(|report| {
+            report
+                .attachments()
+                .iter()
+                .find_map(|attachment| attachment.downcast_inner::<Diagnostics>())
+        })
+    }
+}
+
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} diagnostics", self.process)?;
+        if let Some(status) = &self.exit_status {
+            write!(f, " ({status})")?;
+        }
+        if self.findings.is_empty() {
+            f.write_str("\nNo known issues detected.")?;
+        } else {
+            f.write_str("\nDetected issues:")?;
+            for finding in &self.findings {
+                write!(f, "\n  - {}", finding.issue)?;
+            }
+        }
+        write!(f, "\nLast {} lines of output", self.output.len())?;
+        if self.dropped_lines > 0 {
+            write!(f, " ({} earlier lines dropped)", self.dropped_lines)?;
+        }
+        f.write_str(":")?;
+        for line in &self.output {
+            write!(f, "\n  [{}] {}", line.stream, line.text)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+    use rootcause::report;
+
+    #[test]
+    fn output_buffer_keeps_last_lines() {
+        let mut buffer = OutputBuffer::new(2);
+        buffer.push(OutputStream::Stdout, "one");
+        buffer.push(OutputStream::Stderr, "two");
+        buffer.push(OutputStream::Stdout, "three");
+
+        let texts = buffer
+            .lines()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>();
+        assert_that!(texts).is_equal_to(vec!["two", "three"]);
+        assert_that!(buffer.dropped()).is_equal_to(1);
+    }
+
+    #[test]
+    fn output_buffer_truncates_long_lines() {
+        let mut buffer = OutputBuffer::new(1);
+        buffer.push(OutputStream::Stdout, &"é".repeat(MAX_LINE_LEN));
+
+        let line = buffer.lines().next().unwrap();
+        assert_that!(line.text.as_str()).ends_with("... (4096 bytes truncated)");
+    }
+
+    #[test]
+    fn detects_known_issues() {
+        let cases = [
+            (
+                "/opt/chrome-linux64/chrome: error while loading shared libraries: libnss3.so: cannot open shared object file: No such file or directory",
+                Some(KnownIssue::MissingSharedLibrary {
+                    library: String::from("libnss3.so"),
+                }),
+            ),
+            (
+                "[0101/000000.000000:FATAL:zygote_host_impl_linux.cc(127)] No usable sandbox! Update your kernel or see https://chromium.googlesource.com/chromium/src/+/main/docs/linux/suid_sandbox_development.md for more information on developing with the SUID sandbox.",
+                Some(KnownIssue::SandboxUnavailable),
+            ),
+            (
+                "[0101/000000.000000:ERROR:ozone_platform_x11.cc(240)] Missing X server or $DISPLAY",
+                Some(KnownIssue::NoDisplay),
+            ),
+            (
+                "[0101/000000.000000:FATAL:shared_memory_posix.cc(157)] Creating shared memory in /dev/shm/.org.chromium.Chromium.Xg8kTz failed: No space left on device (28)",
+                Some(KnownIssue::SharedMemoryExhausted),
+            ),
+            (
+                "session not created: Chrome failed to start: exited normally. (session not created: DevToolsActivePort file doesn't exist)",
+                Some(KnownIssue::DevToolsActivePortMissing),
+            ),
+            (
+                "session not created: This version of ChromeDriver only supports Chrome version 141",
+                Some(KnownIssue::VersionMismatch),
+            ),
+            ("ChromeDriver was started successfully on port 9515.", None),
+        ];
+        for (line, expected) in cases {
+            assert_that!(KnownIssue::detect(line)).is_equal_to(expected);
+        }
+    }
+
+    #[test]
+    fn collects_findings_once_and_renders_summary() {
+        let mut buffer = OutputBuffer::new(3);
+        buffer.push(OutputStream::Stdout, "Starting ChromeDriver 141.0.7390.76");
+        buffer.push(
+            OutputStream::Stderr,
+            "chrome: error while loading shared libraries: libgbm.so.1: cannot open shared object file",
+        );
+        buffer.push(
+            OutputStream::Stderr,
+            "chrome: error while loading shared libraries: libgbm.so.1: cannot open shared object file",
+        );
+        buffer.push(OutputStream::Stderr, "Missing X server or $DISPLAY");
+
+        let mut diagnostics = Diagnostics::from_output("Chrome", &buffer);
+        diagnostics.exit_status = Some(String::from("exit status: 127"));
+
+        assert_that!(diagnostics.findings.len()).is_equal_to(2);
+        assert_that!(diagnostics.to_string()).is_equal_to(
+            "Chrome diagnostics (exit status: 127)\n\
+             Detected issues:\n  \
+             - Missing shared library 'libgbm.so.1': install Chrome's system dependencies, e.g. the packages listed in the 'deb.deps' file of the download; see `check_shared_libraries()`\n  \
+             - No display: run headless, or start a virtual display like Xvfb\n\
+             Last 3 lines of output (1 earlier lines dropped):\n  \
+             [stderr] chrome: error while loading shared libraries: libgbm.so.1: cannot open shared object file\n  \
+             [stderr] chrome: error while loading shared libraries: libgbm.so.1: cannot open shared object file\n  \
+             [stderr] Missing X server or $DISPLAY",
+        );
+    }
+
+    #[test]
+    fn finds_diagnostics_attached_to_report() {
+        let diagnostics = Diagnostics::from_output("Chrome", &OutputBuffer::new(1));
+        let err = report!(Error::ChromeStartup {
+            reason: String::from("crashed"),
+        })
+        .attach(diagnostics.clone())
+        .context(Error::ChromeStartup {
+            reason: String::from("outer"),
+        });
+
+        assert_that!(Diagnostics::from_report(&err).cloned()).is_equal_to(Some(diagnostics));
+    }
+}
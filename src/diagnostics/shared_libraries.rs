@@ -0,0 +1,467 @@
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use rootcause::report;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Name of the file listing the Debian packages Chrome depends on, shipped next to the Linux
+/// `chrome` and `chrome-headless-shell` executables.
+pub const DEB_DEPS_FILE: &str = "deb.deps";
+
+/// Packages providing the shared libraries Chrome commonly lacks on minimal Linux images, as
+/// (soname, Debian/Ubuntu package, Fedora package).
+const KNOWN_LIBRARIES: &[(&str, &str, &str)] = &[
+    ("libasound.so.2", "libasound2", "alsa-lib"),
+    ("libatk-1.0.so.0", "libatk1.0-0", "atk"),
+    (
+        "libatk-bridge-2.0.so.0",
+        "libatk-bridge2.0-0",
+        "at-spi2-atk",
+    ),
+    ("libatspi.so.0", "libatspi2.0-0", "at-spi2-core"),
+    ("libcairo.so.2", "libcairo2", "cairo"),
+    ("libcups.so.2", "libcups2", "cups-libs"),
+    ("libdbus-1.so.3", "libdbus-1-3", "dbus-libs"),
+    ("libdrm.so.2", "libdrm2", "libdrm"),
+    ("libexpat.so.1", "libexpat1", "expat"),
+    ("libgbm.so.1", "libgbm1", "mesa-libgbm"),
+    ("libgio-2.0.so.0", "libglib2.0-0", "glib2"),
+    ("libglib-2.0.so.0", "libglib2.0-0", "glib2"),
+    ("libgobject-2.0.so.0", "libglib2.0-0", "glib2"),
+    ("libgtk-3.so.0", "libgtk-3-0", "gtk3"),
+    ("libnspr4.so", "libnspr4", "nspr"),
+    ("libnss3.so", "libnss3", "nss"),
+    ("libnssutil3.so", "libnss3", "nss-util"),
+    ("libpango-1.0.so.0", "libpango-1.0-0", "pango"),
+    ("libsmime3.so", "libnss3", "nss"),
+    ("libudev.so.1", "libudev1", "systemd-libs"),
+    ("libX11.so.6", "libx11-6", "libX11"),
+    ("libxcb.so.1", "libxcb1", "libxcb"),
+    ("libXcomposite.so.1", "libxcomposite1", "libXcomposite"),
+    ("libXdamage.so.1", "libxdamage1", "libXdamage"),
+    ("libXext.so.6", "libxext6", "libXext"),
+    ("libXfixes.so.3", "libxfixes3", "libXfixes"),
+    ("libxkbcommon.so.0", "libxkbcommon0", "libxkbcommon"),
+    ("libXrandr.so.2", "libxrandr2", "libXrandr"),
+];
+
+/// The result of [`check_shared_libraries`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedLibraryReport {
+    /// The checked executable.
+    pub executable: PathBuf,
+
+    /// The shared libraries the dynamic loader could not find.
+    pub missing: Vec<MissingLibrary>,
+
+    /// The dependencies listed in the `deb.deps` file next to the executable, if there is one.
+    pub deb_dependencies: Vec<DebDependency>,
+}
+
+/// A shared library the dynamic loader could not find.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MissingLibrary {
+    /// The soname of the library, e.g. "libnss3.so".
+    pub soname: String,
+
+    /// The Debian/Ubuntu package providing the library, if known.
+    pub debian_package: Option<String>,
+
+    /// The Fedora package providing the library, if known.
+    pub fedora_package: Option<String>,
+}
+
+/// A line of a `deb.deps` file, e.g. "libnss3 (>= 2:3.35)".
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DebDependency {
+    /// The acceptable packages, any of which satisfies the dependency. Usually just one.
+    pub alternatives: Vec<DebPackage>,
+}
+
+/// A Debian package with an optional version constraint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DebPackage {
+    /// The package name, e.g. "libnss3".
+    pub name: String,
+
+    /// The version constraint, e.g. ">= 2:3.35".
+    pub version: Option<String>,
+}
+
+impl DebDependency {
+    /// Parses the contents of a `deb.deps` file, one dependency per line, alternatives separated
+    /// by `|`. Blank lines are skipped.
+    #[must_use]
+    pub fn parse_all(deb_deps: &str) -> Vec<Self> {
+        deb_deps
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| DebDependency {
+                alternatives: line.split('|').map(DebPackage::parse).collect(),
+            })
+            .collect()
+    }
+
+    /// Whether any of the alternatives is the package named `name`.
+    #[must_use]
+    pub fn accepts(&self, name: &str) -> bool {
+        self.alternatives.iter().any(|package| package.name == name)
+    }
+}
+
+impl DebPackage {
+    fn parse(text: &str) -> Self {
+        let text = text.trim();
+        match text.split_once('(') {
+            Some((name, version)) => DebPackage {
+                name: name.trim().to_owned(),
+                version: Some(version.trim_end_matches(')').trim().to_owned()),
+            },
+            None => DebPackage {
+                name: text.to_owned(),
+                version: None,
+            },
+        }
+    }
+}
+
+impl SharedLibraryReport {
+    /// Whether all shared libraries were found.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// The Debian/Ubuntu packages to install for the missing libraries.
+    ///
+    /// A package known to provide a missing library is looked up among all alternatives listed in
+    /// `deb.deps`. If found, the first alternative of that dependency is suggested, as that is
+    /// the upstream preference, otherwise the known package itself.
+    #[must_use]
+    pub fn debian_packages(&self) -> Vec<String> {
+        dedup(
+            self.missing
+                .iter()
+                .filter_map(|library| library.debian_package.as_deref())
+                .map(|package| {
+                    self.deb_dependencies
+                        .iter()
+                        .find(|dependency| dependency.accepts(package))
+                        .and_then(|dependency| dependency.alternatives.first())
+                        .map_or(package, |preferred| preferred.name.as_str())
+                        .to_owned()
+                }),
+        )
+    }
+
+    /// All Debian/Ubuntu packages listed in `deb.deps`, taking the first alternative of each
+    /// dependency. Empty if there is no `deb.deps` file.
+    ///
+    /// This is the complete upstream list, also covering libraries which are present.
+    #[must_use]
+    pub fn all_deb_dependencies(&self) -> Vec<String> {
+        dedup(
+            self.deb_dependencies
+                .iter()
+                .filter_map(|dependency| dependency.alternatives.first())
+                .map(|package| package.name.clone()),
+        )
+    }
+
+    /// The Fedora packages known to provide the missing libraries.
+    #[must_use]
+    pub fn fedora_packages(&self) -> Vec<String> {
+        dedup(
+            self.missing
+                .iter()
+                .filter_map(|library| library.fedora_package.clone()),
+        )
+    }
+}
+
+fn dedup(packages: impl Iterator<Item = String>) -> Vec<String> {
+    let mut unique = Vec::new();
+    for package in packages {
+        if !unique.contains(&package) {
+            unique.push(package);
+        }
+    }
+    unique
+}
+
+impl Display for SharedLibraryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            return write!(
+                f,
+                "All shared libraries of '{}' were found.",
+                self.executable.display()
+            );
+        }
+        write!(
+            f,
+            "'{}' is missing {} shared libraries:",
+            self.executable.display(),
+            self.missing.len()
+        )?;
+        for library in &self.missing {
+            write!(f, "\n  - {}", library.soname)?;
+        }
+        let debian_packages = self.debian_packages();
+        if !debian_packages.is_empty() {
+            write!(
+                f,
+                "\nDebian/Ubuntu: apt-get install {}",
+                debian_packages.join(" ")
+            )?;
+        }
+        let fedora_packages = self.fedora_packages();
+        if !fedora_packages.is_empty() {
+            write!(f, "\nFedora: dnf install {}", fedora_packages.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks which shared libraries of the Linux executable at `executable` cannot be found, e.g.
+/// an extracted `chrome` or `chrome-headless-shell`, by running `ldd`.
+///
+/// A `deb.deps` file next to the executable is read to prefer the package names listed
+/// upstream.
+///
+/// ```no_run
+/// use chrome_for_testing::diagnostics::check_shared_libraries;
+///
+/// # fn main() -> chrome_for_testing::Result<()> {
+/// let report = check_shared_libraries("/opt/cft/chrome-linux64/chrome")?;
+/// if !report.is_ok() {
+///     eprintln!("{report}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `ldd` cannot be run or fails, e.g. because the executable does not exist
+/// or is not a dynamic executable, or if `deb.deps` exists but cannot be read.
+pub fn check_shared_libraries(executable: impl AsRef<Path>) -> crate::Result<SharedLibraryReport> {
+    check_with(executable.as_ref(), OsStr::new("ldd"))
+}
+
+fn check_with(executable: &Path, ldd: &OsStr) -> crate::Result<SharedLibraryReport> {
+    let output = Command::new(ldd)
+        .arg(executable)
+        .output()
+        .context_to::<Error>()
+        .attach_with(|| format!("while running ldd on '{}'", executable.display()))?;
+    if !output.status.success() {
+        return Err(report!(Error::SharedLibraryCheck {
+            executable: executable.to_owned(),
+            reason: format!(
+                "ldd failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        }));
+    }
+
+    let deb_deps_path = executable
+        .parent()
+        .map(|dir| dir.join(DEB_DEPS_FILE))
+        .filter(|path| path.is_file());
+    let deb_dependencies = match deb_deps_path {
+        Some(path) => DebDependency::parse_all(
+            &std::fs::read_to_string(&path)
+                .context_to::<Error>()
+                .attach_with(|| format!("while reading '{}'", path.display()))?,
+        ),
+        None => Vec::new(),
+    };
+
+    Ok(SharedLibraryReport {
+        executable: executable.to_owned(),
+        missing: parse_ldd_output(&String::from_utf8_lossy(&output.stdout)),
+        deb_dependencies,
+    })
+}
+
+/// Extracts the missing libraries from lines like "`libnss3.so => not found`".
+fn parse_ldd_output(output: &str) -> Vec<MissingLibrary> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (soname, resolved) = line.split_once("=>")?;
+            (resolved.trim() == "not found").then(|| soname.trim())
+        })
+        .map(|soname| {
+            let known = KNOWN_LIBRARIES
+                .iter()
+                .find(|(known_soname, _, _)| *known_soname == soname);
+            MissingLibrary {
+                soname: soname.to_owned(),
+                debian_package: known.map(|(_, debian, _)| (*debian).to_owned()),
+                fedora_package: known.map(|(_, _, fedora)| (*fedora).to_owned()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    const LDD_OUTPUT: &str = "\tlinux-vdso.so.1 (0x00007ffd4b3f2000)
+\tlibdl.so.2 => /lib/x86_64-linux-gnu/libdl.so.2 (0x00007f0b1c1a0000)
+\tlibnss3.so => not found
+\tlibnssutil3.so => not found
+\tlibgbm.so.1 => not found
+\tlibfoo.so.7 => not found
+\tlibc.so.6 => /lib/x86_64-linux-gnu/libc.so.6 (0x00007f0b1bf80000)
+";
+
+    #[test]
+    fn parses_missing_libraries_from_ldd_output() {
+        let missing = parse_ldd_output(LDD_OUTPUT);
+
+        assert_that!(missing).is_equal_to(vec![
+            MissingLibrary {
+                soname: String::from("libnss3.so"),
+                debian_package: Some(String::from("libnss3")),
+                fedora_package: Some(String::from("nss")),
+            },
+            MissingLibrary {
+                soname: String::from("libnssutil3.so"),
+                debian_package: Some(String::from("libnss3")),
+                fedora_package: Some(String::from("nss-util")),
+            },
+            MissingLibrary {
+                soname: String::from("libgbm.so.1"),
+                debian_package: Some(String::from("libgbm1")),
+                fedora_package: Some(String::from("mesa-libgbm")),
+            },
+            MissingLibrary {
+                soname: String::from("libfoo.so.7"),
+                debian_package: None,
+                fedora_package: None,
+            },
+        ]);
+    }
+
+    #[test]
+    fn parses_deb_deps() {
+        let dependencies = DebDependency::parse_all(
+            "libasound2 (>= 1.0.17)\nlibc6 (>= 2.17)\n\nlibgtk-3-0 (>= 3.9.10) | libgtk-4-1\n",
+        );
+
+        assert_that!(dependencies).is_equal_to(vec![
+            DebDependency {
+                alternatives: vec![DebPackage {
+                    name: String::from("libasound2"),
+                    version: Some(String::from(">= 1.0.17")),
+                }],
+            },
+            DebDependency {
+                alternatives: vec![DebPackage {
+                    name: String::from("libc6"),
+                    version: Some(String::from(">= 2.17")),
+                }],
+            },
+            DebDependency {
+                alternatives: vec![
+                    DebPackage {
+                        name: String::from("libgtk-3-0"),
+                        version: Some(String::from(">= 3.9.10")),
+                    },
+                    DebPackage {
+                        name: String::from("libgtk-4-1"),
+                        version: None,
+                    },
+                ],
+            },
+        ]);
+    }
+
+    #[test]
+    fn suggests_packages_for_missing_libraries() {
+        let report = SharedLibraryReport {
+            executable: PathBuf::from("/opt/cft/chrome-linux64/chrome"),
+            missing: parse_ldd_output(LDD_OUTPUT),
+            deb_dependencies: Vec::new(),
+        };
+
+        assert_that!(report.is_ok()).is_false();
+        assert_that!(report.debian_packages())
+            .is_equal_to(vec![String::from("libnss3"), String::from("libgbm1")]);
+        assert_that!(report.to_string()).is_equal_to(
+            "'/opt/cft/chrome-linux64/chrome' is missing 4 shared libraries:\n  \
+             - libnss3.so\n  \
+             - libnssutil3.so\n  \
+             - libgbm.so.1\n  \
+             - libfoo.so.7\n\
+             Debian/Ubuntu: apt-get install libnss3 libgbm1\n\
+             Fedora: dnf install nss nss-util mesa-libgbm",
+        );
+    }
+
+    #[cfg(unix)]
+    mod fake_ldd {
+        use super::*;
+        use std::os::unix::fs::PermissionsExt;
+
+        fn fake_ldd(dir: &tempfile::TempDir, script: &str) -> PathBuf {
+            let path = dir.path().join("ldd");
+            std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        }
+
+        #[test]
+        fn prefers_package_names_from_deb_deps_next_to_executable() {
+            let dir = tempfile::tempdir().unwrap();
+            let ldd = fake_ldd(&dir, "printf '\\tlibnss3.so => not found\\n'");
+            let chrome_dir = dir.path().join("chrome-linux64");
+            std::fs::create_dir(&chrome_dir).unwrap();
+            std::fs::write(
+                chrome_dir.join(DEB_DEPS_FILE),
+                "libasound2 (>= 1.0.17)\nlibnss3t64 (>= 2:3.35) | libnss3 (>= 2:3.35)\n",
+            )
+            .unwrap();
+
+            let report = check_with(&chrome_dir.join("chrome"), ldd.as_os_str()).unwrap();
+
+            assert_that!(report.missing.len()).is_equal_to(1);
+            assert_that!(report.deb_dependencies.len()).is_equal_to(2);
+            assert_that!(report.debian_packages()).is_equal_to(vec![String::from("libnss3t64")]);
+            assert_that!(report.all_deb_dependencies())
+                .is_equal_to(vec![String::from("libasound2"), String::from("libnss3t64")]);
+        }
+
+        #[test]
+        fn reports_failing_ldd() {
+            let dir = tempfile::tempdir().unwrap();
+            let ldd = fake_ldd(&dir, "echo 'not a dynamic executable' >&2\nexit 1");
+            let executable = dir.path().join("chrome");
+
+            let err = check_with(&executable, ldd.as_os_str()).unwrap_err();
+
+            let Error::SharedLibraryCheck {
+                executable: checked,
+                reason,
+            } = err.current_context()
+            else {
+                panic!(
+                    "expected shared library check error, got: {:?}",
+                    err.current_context()
+                );
+            };
+            assert_that!(checked.clone()).is_equal_to(executable);
+            assert_that!(reason.as_str()).ends_with("not a dynamic executable");
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use crate::api::binary::Binary;
+use crate::api::platform::Platform;
+use crate::api::version::Version;
 use rootcause::{Report, ReportConversion, markers};
 use std::borrow::Cow;
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Errors that can occur when using this crate.
@@ -10,9 +14,53 @@ pub enum Error {
     UrlParsing(#[from] url::ParseError),
 
     /// An HTTP request failed.
+    #[cfg(feature = "reqwest")]
     #[error("HTTP request failed: {0}")]
     Request(#[from] reqwest::Error),
 
+    /// An HTTP backend other than `reqwest` failed to perform a request.
+    #[error("HTTP transport failed: {0}")]
+    Transport(Box<dyn std::error::Error + Send + Sync + 'static>),
+
+    /// An HTTP request timed out.
+    #[error("HTTP request timed out: {0}")]
+    Timeout(Box<dyn std::error::Error + Send + Sync + 'static>),
+
+    /// An endpoint or download responded with an unsuccessful HTTP status code.
+    #[error(
+        "Chrome for Testing {endpoint} endpoint responded with HTTP status {status}: {body_snippet}"
+    )]
+    HttpStatus {
+        /// The name of the requested endpoint, e.g. "`KnownGoodVersions`", or "`Download`" for
+        /// archive downloads.
+        endpoint: &'static str,
+
+        /// The HTTP status code.
+        status: u16,
+
+        /// The beginning of the response body, lossily decoded as UTF-8.
+        body_snippet: String,
+    },
+
+    /// An endpoint response body could not be deserialized, e.g. because the upstream schema
+    /// changed.
+    #[error("Failed to deserialize Chrome for Testing {endpoint} response at '{path}': {source}")]
+    Deserialization {
+        /// The name of the requested endpoint, e.g. "`KnownGoodVersions`".
+        endpoint: &'static str,
+
+        /// The path of the offending value inside the response, e.g.
+        /// "`versions[3].downloads.chrome[0].platform`".
+        path: String,
+
+        /// The underlying deserialization error.
+        source: serde_json::Error,
+    },
+
+    /// An I/O operation failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// The current platform is not supported by `chrome-for-testing`.
     #[error("Platform (os: {os}, arch: {arch}) is not supported.")]
     UnsupportedPlatform {
@@ -22,6 +70,180 @@ pub enum Error {
         /// The system architecture name, e.g. "`x86_64`".
         arch: Cow<'static, str>,
     },
+
+    /// A platform override environment variable holds an unknown platform name.
+    #[error("Environment variable {env_var} holds an unknown platform: '{value}'.")]
+    InvalidPlatformOverride {
+        /// The name of the environment variable.
+        env_var: String,
+
+        /// The value of the environment variable.
+        value: String,
+    },
+
+    /// A log level environment variable holds an unknown level.
+    #[error("Environment variable {env_var} holds an unknown log level: '{value}'.")]
+    InvalidLogLevelOverride {
+        /// The name of the environment variable.
+        env_var: String,
+
+        /// The value of the environment variable.
+        value: String,
+    },
+
+    /// The version of a Chrome executable could not be determined from its output.
+    #[error("Could not determine the version of Chrome executable '{}' from its output: '{output}'", executable.display())]
+    UnrecognizedChromeVersion {
+        /// The path of the queried executable.
+        executable: PathBuf,
+
+        /// The output of the version query.
+        output: String,
+    },
+
+    /// No known-good `ChromeDriver` matches a Chrome version.
+    #[error(
+        "No ChromeDriver download matching Chrome {chrome} is available for platform '{platform}'."
+    )]
+    NoMatchingChromeDriver {
+        /// The Chrome version to match.
+        chrome: Version,
+
+        /// The requested platform.
+        platform: Platform,
+    },
+
+    /// `ChromeDriver` did not start successfully.
+    #[error("ChromeDriver failed to start: {reason}")]
+    ChromeDriverStartup {
+        /// Why the start is considered failed.
+        reason: String,
+    },
+
+    /// Chrome did not announce its `DevTools` endpoint.
+    #[error("Chrome failed to start: {reason}")]
+    ChromeStartup {
+        /// Why the start is considered failed.
+        reason: String,
+    },
+
+    /// A WebDriver server rejected a command or responded with something other than a WebDriver
+    /// response.
+    #[error("WebDriver {command} command failed with '{error}': {message}")]
+    WebDriver {
+        /// The name of the command, e.g. "`NewSession`".
+        command: &'static str,
+
+        /// The W3C error code, e.g. "session not created", or the HTTP status if the response
+        /// was no WebDriver error.
+        error: String,
+
+        /// The error message, or the beginning of the response body.
+        message: String,
+    },
+
+    /// A downloaded archive could not be unpacked.
+    #[error("Invalid archive: {reason}")]
+    InvalidArchive {
+        /// Why the archive was rejected.
+        reason: String,
+    },
+
+    /// A freshly installed browser and driver did not pass the smoke test.
+    #[error("Installation verification failed: {reason}")]
+    InstallVerification {
+        /// Why the installation is considered broken.
+        reason: String,
+    },
+
+    /// The shared libraries of an executable could not be checked.
+    #[error("Could not check the shared libraries of '{}': {reason}", executable.display())]
+    SharedLibraryCheck {
+        /// The path of the checked executable.
+        executable: PathBuf,
+
+        /// Why the check failed.
+        reason: String,
+    },
+
+    /// A line of a channel history file is no valid snapshot.
+    #[error("Channel history '{}' is corrupt at line {line}: {source}", path.display())]
+    CorruptChannelHistory {
+        /// The path of the history file.
+        path: PathBuf,
+
+        /// The offending line, starting at 1.
+        line: usize,
+
+        /// The underlying deserialization error.
+        source: serde_json::Error,
+    },
+
+    /// A bisection was started with a good version not older than the bad version.
+    #[error(
+        "Cannot bisect from good version {good} to bad version {bad}: the good version must be older."
+    )]
+    InvalidBisectRange {
+        /// The version known to be good.
+        good: Version,
+
+        /// The version known to be bad.
+        bad: Version,
+    },
+
+    /// No download of a binary is available for the requested platform.
+    #[error("No {binary} download is available for platform '{platform}'.")]
+    NoDownloadForPlatform {
+        /// The requested binary.
+        binary: Binary,
+
+        /// The requested platform.
+        platform: Platform,
+    },
+}
+
+impl Error {
+    /// Wraps a failure of an HTTP backend other than `reqwest` into an [`Error::Transport`].
+    ///
+    /// Accepts any error type as well as plain messages, e.g. `Error::transport("connection
+    /// reset")`.
+    pub fn transport(error: impl Into<Box<dyn std::error::Error + Send + Sync + 'static>>) -> Self {
+        Error::Transport(error.into())
+    }
+
+    /// Tells whether this error is likely temporary, e.g. because the upstream service is
+    /// unreachable, timed out, or responded with a server error.
+    ///
+    /// Transient errors are good candidates for retrying or falling back to cached data, whereas
+    /// other errors (like [`Error::Deserialization`]) indicate a persistent problem.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            #[cfg(feature = "reqwest")]
+            // Reports converted with `context_to` carry timeouts as `Error::Timeout`, but
+            // `Error::from` still wraps them as requests.
+            Error::Request(error) => error.is_connect() || error.is_timeout(),
+            Error::Transport(_) | Error::Timeout(_) => true,
+            Error::HttpStatus { status, .. } => *status == 429 || *status >= 500,
+            Error::UrlParsing(_)
+            | Error::Deserialization { .. }
+            | Error::Io(_)
+            | Error::UnsupportedPlatform { .. }
+            | Error::InvalidPlatformOverride { .. }
+            | Error::InvalidLogLevelOverride { .. }
+            | Error::UnrecognizedChromeVersion { .. }
+            | Error::NoMatchingChromeDriver { .. }
+            | Error::ChromeDriverStartup { .. }
+            | Error::ChromeStartup { .. }
+            | Error::WebDriver { .. }
+            | Error::InvalidArchive { .. }
+            | Error::InstallVerification { .. }
+            | Error::SharedLibraryCheck { .. }
+            | Error::CorruptChannelHistory { .. }
+            | Error::InvalidBisectRange { .. }
+            | Error::NoDownloadForPlatform { .. } => false,
+        }
+    }
 }
 
 impl<T> ReportConversion<url::ParseError, markers::Mutable, T> for Error
@@ -35,6 +257,7 @@ where
     }
 }
 
+#[cfg(feature = "reqwest")]
 impl<T> ReportConversion<reqwest::Error, markers::Mutable, T> for Error
 where
     Error: markers::ObjectMarkerFor<T>,
@@ -42,6 +265,41 @@ where
     fn convert_report(
         report: Report<reqwest::Error, markers::Mutable, T>,
     ) -> Report<Self, markers::Mutable, T> {
-        report.context_transform(Error::Request)
+        report.context_transform(|error| {
+            if error.is_timeout() {
+                Error::Timeout(Box::new(error))
+            } else {
+                Error::Request(error)
+            }
+        })
+    }
+}
+
+impl<T> ReportConversion<std::io::Error, markers::Mutable, T> for Error
+where
+    Error: markers::ObjectMarkerFor<T>,
+{
+    fn convert_report(
+        report: Report<std::io::Error, markers::Mutable, T>,
+    ) -> Report<Self, markers::Mutable, T> {
+        report.context_transform(Error::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn transport_wraps_errors_and_messages() {
+        let from_error = Error::transport(std::io::Error::other("connection reset"));
+        let from_message = Error::transport("connection reset");
+
+        for error in [from_error, from_message] {
+            assert_that!(error.to_string())
+                .is_equal_to(String::from("HTTP transport failed: connection reset"));
+            assert_that!(error.is_transient()).is_true();
+        }
     }
 }
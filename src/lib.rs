@@ -6,7 +6,15 @@
 //!
 //! ## Modules Overview
 //!
-//! - [`chromedriver`]: `ChromeDriver` specific utilities, such as log level configuration.
+//! - [`bisect`]: Binary search over known-good versions for the first one showing a regression.
+//! - [`chrome`]: Chrome specific utilities, such as determining the version of an installed
+//!   executable and building command-line flags.
+//! - [`chromedriver`]: `ChromeDriver` specific utilities, such as log level configuration,
+//!   process launching and log parsing.
+//! - [`diagnostics`]: Captured output and recognized issues of failed Chrome and `ChromeDriver`
+//!   processes.
+//! - [`webdriver`]: W3C WebDriver types, such as capabilities with `goog:chromeOptions`, and a
+//!   minimal session client for verifying installations.
 //!
 //! ## API Endpoints
 //!
@@ -31,10 +39,13 @@
 //! - **Type-Safe Deserialization**: Automatically maps JSON responses to Rust structs for
 //!   seamless API interaction.
 //! - **Asynchronous Support**: Fully asynchronous.
+//! - **Pluggable HTTP Backends**: The [`Endpoint`] trait separates request construction and response
+//!   parsing from I/O. The default `reqwest` feature provides ready-made `fetch` functions on top.
 //!
 //! ## Example Usage
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
 //! #[tokio::main]
 //! async fn main() {
 //!     use chrome_for_testing::KnownGoodVersions;
@@ -45,19 +56,57 @@
 //!         Err(e) => println!("Error occurred: {e:?}"),
 //!     }
 //! }
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() {}
 //! ```
 
-/// `ChromeDriver` specific utilities, such as log level configuration.
+/// Chrome specific utilities, such as determining the version of an installed executable and
+/// building command-line flags.
+pub mod chrome;
+
+/// Binary search over known-good versions for the first one showing a regression.
+pub mod bisect;
+
+/// `ChromeDriver` specific utilities, such as log level configuration, process launching and log
+/// parsing.
 pub mod chromedriver;
 
+/// Plain-data diagnostics about failed Chrome and `ChromeDriver` processes: captured output and
+/// recognized issues like missing shared libraries.
+pub mod diagnostics;
+
+/// W3C WebDriver types, such as capabilities with `goog:chromeOptions`, and a minimal session
+/// client for verifying installations.
+pub mod webdriver;
+
 pub(crate) mod api;
+#[cfg(feature = "reqwest")]
+pub(crate) mod client;
 pub(crate) mod error;
+pub(crate) mod process;
+pub(crate) mod progress;
 
 pub use api::Download;
 pub use api::DownloadsByPlatform;
 pub use api::HasVersion;
+pub use api::ResolvedDownload;
+pub use api::archive::extract_archive;
+pub use api::binary::Binary;
 pub use api::channel::Channel;
 pub use api::channel::ParseChannelError;
+pub use api::detection::DetectOptions;
+pub use api::detection::DetectedPlatform;
+pub use api::detection::DetectionSource;
+pub use api::detection::PLATFORM_OVERRIDE_ENV_VAR;
+pub use api::endpoint::Endpoint;
+pub use api::endpoint::HttpRequest;
+pub use api::endpoint::Parsed;
+pub use api::endpoint::SkippedDownload;
+pub use api::history::ChannelHistory;
+pub use api::history::ChannelPromotion;
+pub use api::history::ChannelSnapshot;
+pub use api::history::ChannelVersion;
+pub use api::known_good_versions::ChromeDriverMatch;
 pub use api::known_good_versions::Downloads as KnownGoodDownloads;
 pub use api::known_good_versions::KnownGoodVersions;
 pub use api::known_good_versions::VersionWithoutChannel;
@@ -66,9 +115,18 @@ pub use api::last_known_good_versions::LastKnownGoodVersions;
 pub use api::last_known_good_versions::VersionInChannel;
 pub use api::platform::ParsePlatformError;
 pub use api::platform::Platform;
+pub use api::version::Milestone;
 pub use api::version::ParseVersionError;
+pub use api::version::PartialVersion;
 pub use api::version::Version;
+pub use api::version::VersionMatch;
+#[cfg(feature = "reqwest")]
+pub use client::ClientConfig;
 pub use error::Error;
+pub use progress::NoProgress;
+pub use progress::Progress;
+pub use progress::ProgressPhase;
+pub use progress::ProgressReporter;
 
 /// Result type returned by fallible crate APIs.
 pub type Result<T, E = Error> = std::result::Result<T, rootcause::Report<E>>;
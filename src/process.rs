@@ -0,0 +1,137 @@
+use crate::diagnostics::{Diagnostics, OutputBuffer, OutputStream};
+use crate::error::Error;
+use rootcause::prelude::ResultExt;
+use rootcause::{Report, report};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, ExitStatus};
+use std::sync::{Arc, Mutex, PoisonError, mpsc};
+use std::thread;
+
+/// Captures the stdout and stderr of `child`, as far as they are piped, into `buffer` on
+/// background threads, and forwards each line to the returned receiver.
+///
+/// Capturing continues after the receiver is gone, so that the child never blocks on a full pipe
+/// and `buffer` keeps the latest output for diagnostics.
+pub(crate) fn capture_output(
+    child: &mut Child,
+    buffer: &Arc<Mutex<OutputBuffer>>,
+) -> mpsc::Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, OutputStream::Stdout, buffer.clone(), sender.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, OutputStream::Stderr, buffer.clone(), sender);
+    }
+    receiver
+}
+
+fn forward_lines(
+    source: impl Read + Send + 'static,
+    stream: OutputStream,
+    buffer: Arc<Mutex<OutputBuffer>>,
+    sender: mpsc::Sender<String>,
+) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(source);
+        let mut bytes = Vec::new();
+        // Reading only stops at EOF or a read error. Output that is no valid UTF-8 is replaced
+        // lossily rather than ending the capture, which would leave the pipe undrained.
+        while reader
+            .read_until(b'\n', &mut bytes)
+            .is_ok_and(|read| read > 0)
+        {
+            let line = String::from_utf8_lossy(strip_line_ending(&bytes)).into_owned();
+            bytes.clear();
+            if let Ok(mut buffer) = buffer.lock() {
+                buffer.push(stream, &line);
+            }
+            let _ = sender.send(line);
+        }
+    });
+}
+
+fn strip_line_ending(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Collects [`Diagnostics`] from the output captured so far.
+pub(crate) fn collect_diagnostics(
+    process: &str,
+    output: &Mutex<OutputBuffer>,
+    exit_status: Option<ExitStatus>,
+) -> Diagnostics {
+    let output = output.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut diagnostics = Diagnostics::from_output(process, &output);
+    diagnostics.exit_status = exit_status.map(|status| status.to_string());
+    diagnostics
+}
+
+/// Kills `child`, which failed to start, and reports `error` with [`Diagnostics`] about the
+/// output of `process`.
+pub(crate) fn startup_failure(
+    child: &mut Child,
+    process: &str,
+    output: &Mutex<OutputBuffer>,
+    exit_status: Option<ExitStatus>,
+    error: Error,
+) -> Report<Error> {
+    let _ = child.kill();
+    let _ = child.wait();
+    report!(error).attach(collect_diagnostics(process, output, exit_status))
+}
+
+/// Kills `child` unless it already exited, and waits for it to exit.
+pub(crate) fn terminate(child: &mut Child, process: &str) -> crate::Result<()> {
+    if child
+        .try_wait()
+        .context_to::<Error>()
+        .attach_with(|| format!("while checking {process} process status"))?
+        .is_some()
+    {
+        return Ok(());
+    }
+    child
+        .kill()
+        .context_to::<Error>()
+        .attach_with(|| format!("while killing {process} process"))?;
+    child
+        .wait()
+        .context_to::<Error>()
+        .attach_with(|| format!("while waiting for {process} process to exit"))?;
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn keeps_forwarding_lines_after_invalid_utf8() {
+        let mut child = Command::new("sh")
+            .args([
+                "-c",
+                r"printf 'before\n\377invalid\r\nafter\n'; printf 'last' >&2",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let buffer = Arc::new(Mutex::new(OutputBuffer::default()));
+
+        let receiver = capture_output(&mut child, &buffer);
+        let mut lines = receiver.iter().collect::<Vec<_>>();
+        child.wait().unwrap();
+        lines.sort();
+
+        assert_that!(lines).is_equal_to(vec![
+            String::from("after"),
+            String::from("before"),
+            String::from("last"),
+            String::from("\u{FFFD}invalid"),
+        ]);
+    }
+}
@@ -0,0 +1,115 @@
+/// Phase of a binary installation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProgressPhase {
+    /// Archive bytes are being received.
+    Downloading,
+
+    /// The downloaded archive is being unpacked.
+    Extracting,
+}
+
+/// A single progress update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Progress {
+    /// The phase this update belongs to.
+    pub phase: ProgressPhase,
+
+    /// Number of bytes processed in this phase so far.
+    pub bytes_processed: u64,
+
+    /// Total number of bytes expected in this phase, if known.
+    ///
+    /// For downloads, this is taken from the `Content-Length` response header.
+    pub total_bytes: Option<u64>,
+}
+
+impl Progress {
+    /// Returns the completed fraction of this phase in the range `0.0..=1.0`, if the total is
+    /// known.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total_bytes {
+            Some(0) => Some(1.0),
+            Some(total) => Some((self.bytes_processed as f64 / total as f64).min(1.0)),
+            None => None,
+        }
+    }
+}
+
+/// Receives progress updates during binary installation.
+///
+/// Implement this to drive a progress bar or to emit periodic log lines. Closures taking a
+/// [`Progress`] implement this trait as well.
+///
+/// ```
+/// use chrome_for_testing::{Progress, ProgressReporter};
+///
+/// struct LogReporter;
+///
+/// impl ProgressReporter for LogReporter {
+///     fn report(&self, progress: Progress) {
+///         println!("{:?}: {} bytes", progress.phase, progress.bytes_processed);
+///     }
+/// }
+/// ```
+pub trait ProgressReporter {
+    /// Called for every progress update.
+    fn report(&self, progress: Progress);
+}
+
+impl<F> ProgressReporter for F
+where
+    F: Fn(Progress),
+{
+    fn report(&self, progress: Progress) {
+        self(progress);
+    }
+}
+
+/// A [`ProgressReporter`] discarding all updates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoProgress;
+
+impl ProgressReporter for NoProgress {
+    fn report(&self, _progress: Progress) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn fraction_is_none_without_total() {
+        let progress = Progress {
+            phase: ProgressPhase::Downloading,
+            bytes_processed: 42,
+            total_bytes: None,
+        };
+
+        assert_that!(progress.fraction()).is_none();
+    }
+
+    #[test]
+    fn fraction_is_relative_to_total() {
+        let progress = Progress {
+            phase: ProgressPhase::Downloading,
+            bytes_processed: 25,
+            total_bytes: Some(100),
+        };
+
+        assert_that!(progress.fraction()).is_equal_to(Some(0.25));
+    }
+
+    #[test]
+    fn fraction_of_empty_total_is_complete() {
+        let progress = Progress {
+            phase: ProgressPhase::Extracting,
+            bytes_processed: 0,
+            total_bytes: Some(0),
+        };
+
+        assert_that!(progress.fraction()).is_equal_to(Some(1.0));
+    }
+}
@@ -0,0 +1,330 @@
+use crate::api::binary::Binary;
+use crate::api::platform::Platform;
+use crate::chrome::ChromeArgs;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::path::{Path, PathBuf};
+
+/// W3C WebDriver capabilities for Chrome.
+///
+/// Capabilities not modeled here can be added through `extra`.
+///
+/// ```
+/// use chrome_for_testing::chrome::ChromeArgs;
+/// use chrome_for_testing::webdriver::{Capabilities, ChromeOptions};
+/// use chrome_for_testing::{Binary, Platform};
+///
+/// let options = ChromeOptions::for_installation("/opt/chrome", Binary::Chrome, Platform::Linux64)
+///     .with_args(&ChromeArgs::ci_linux_container(), Binary::Chrome);
+/// let payload = Capabilities::chrome(options).new_session_payload();
+/// assert_eq!(
+///     payload["capabilities"]["alwaysMatch"]["goog:chromeOptions"]["binary"],
+///     "/opt/chrome/chrome-linux64/chrome"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// The browser name, "chrome" for Chrome and Chrome Headless Shell.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub browser_name: Option<String>,
+
+    /// The required browser version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub browser_version: Option<String>,
+
+    /// The required platform name, e.g. "linux".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform_name: Option<String>,
+
+    /// Whether untrusted and self-signed TLS certificates are accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accept_insecure_certs: Option<bool>,
+
+    /// Chrome specific options.
+    #[serde(
+        rename = "goog:chromeOptions",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub chrome_options: Option<ChromeOptions>,
+
+    /// Any further capabilities, keyed by their name.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Capabilities {
+    /// Capabilities requesting Chrome with the given options.
+    #[must_use]
+    pub fn chrome(options: ChromeOptions) -> Self {
+        Self {
+            browser_name: Some(String::from("chrome")),
+            chrome_options: Some(options),
+            ..Self::default()
+        }
+    }
+
+    /// The body of a W3C "New Session" request requiring these capabilities, i.e.
+    /// `{"capabilities": {"alwaysMatch": ...}}`.
+    #[must_use]
+    pub fn new_session_payload(&self) -> Value {
+        json!({ "capabilities": { "alwaysMatch": self } })
+    }
+}
+
+/// The `goog:chromeOptions` capability.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChromeOptions {
+    /// Path of the Chrome executable.
+    ///
+    /// Without it, `ChromeDriver` starts the Chrome installed on the system, which rarely matches
+    /// a downloaded Chrome for Testing build.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary: Option<PathBuf>,
+
+    /// Command-line flags Chrome is started with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// User preferences, e.g. `{"download.default_directory": "/tmp"}`.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub prefs: Map<String, Value>,
+
+    /// Base64-encoded packed extensions (`.crx` files) to install.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+
+    /// Emulation of a mobile device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mobile_emulation: Option<MobileEmulation>,
+
+    /// Performance logging preferences.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub perf_logging_prefs: Option<PerfLoggingPrefs>,
+
+    /// Flags `ChromeDriver` passes by default which should be left out, e.g.
+    /// "enable-automation".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_switches: Vec<String>,
+
+    /// Any further options, keyed by their name.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ChromeOptions {
+    /// Options using the executable of `binary`, unpacked into `install_dir` for `platform`.
+    ///
+    /// `binary` should be [`Binary::Chrome`] or [`Binary::ChromeHeadlessShell`].
+    #[must_use]
+    pub fn for_installation(
+        install_dir: impl AsRef<Path>,
+        binary: Binary,
+        platform: Platform,
+    ) -> Self {
+        Self {
+            binary: Some(install_dir.as_ref().join(binary.executable_path(platform))),
+            ..Self::default()
+        }
+    }
+
+    /// Appends the flags of `args`, rendered for `binary` regardless of
+    /// [`ChromeArgs::binary`], e.g. without `--headless=new` for Chrome Headless Shell.
+    ///
+    /// Pass the binary `binary` points to. Flags which are not valid UTF-8 are converted lossily.
+    #[must_use]
+    pub fn with_args(mut self, args: &ChromeArgs, binary: Binary) -> Self {
+        self.args.extend(
+            args.clone()
+                .binary(binary)
+                .to_args()
+                .into_iter()
+                .map(|arg| arg.to_string_lossy().into_owned()),
+        );
+        self
+    }
+}
+
+/// The `mobileEmulation` option, naming either a device known to Chrome `DevTools` or explicit
+/// device metrics.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MobileEmulation {
+    /// A device name from the `DevTools` emulation panel, e.g. "Pixel 7".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+
+    /// Explicit device metrics, used instead of a device name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_metrics: Option<DeviceMetrics>,
+
+    /// The user agent to report, used together with device metrics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+}
+
+/// Screen metrics of an emulated mobile device.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceMetrics {
+    /// The screen width in CSS pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+
+    /// The screen height in CSS pixels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+
+    /// The device pixel ratio.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pixel_ratio: Option<f64>,
+
+    /// Whether touch events are emulated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub touch: Option<bool>,
+}
+
+/// The `perfLoggingPrefs` option.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfLoggingPrefs {
+    /// Whether network domain events are collected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_network: Option<bool>,
+
+    /// Whether page domain events are collected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_page: Option<bool>,
+
+    /// Comma-separated trace categories, e.g. "devtools.timeline".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_categories: Option<String>,
+
+    /// Requested interval between trace buffer usage events, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buffer_usage_reporting_interval: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn serializes_chrome_capabilities() {
+        let options = ChromeOptions {
+            args: vec![String::from("--headless=new")],
+            prefs: Map::from_iter([(
+                String::from("download.default_directory"),
+                json!("/tmp/downloads"),
+            )]),
+            extensions: vec![String::from("Q3IyNA==")],
+            mobile_emulation: Some(MobileEmulation {
+                device_metrics: Some(DeviceMetrics {
+                    width: Some(412),
+                    height: Some(915),
+                    pixel_ratio: Some(2.625),
+                    touch: Some(true),
+                }),
+                user_agent: Some(String::from("Mozilla/5.0 (Linux; Android 14)")),
+                ..MobileEmulation::default()
+            }),
+            perf_logging_prefs: Some(PerfLoggingPrefs {
+                enable_network: Some(true),
+                trace_categories: Some(String::from("devtools.timeline")),
+                ..PerfLoggingPrefs::default()
+            }),
+            exclude_switches: vec![String::from("enable-automation")],
+            ..ChromeOptions::for_installation("/opt/cft", Binary::Chrome, Platform::Linux64)
+        };
+        let mut capabilities = Capabilities::chrome(options);
+        capabilities.accept_insecure_certs = Some(true);
+        capabilities.extra.insert(
+            String::from("goog:loggingPrefs"),
+            json!({ "browser": "ALL" }),
+        );
+
+        assert_that!(capabilities.new_session_payload()).is_equal_to(json!({
+            "capabilities": {
+                "alwaysMatch": {
+                    "browserName": "chrome",
+                    "acceptInsecureCerts": true,
+                    "goog:chromeOptions": {
+                        "binary": "/opt/cft/chrome-linux64/chrome",
+                        "args": ["--headless=new"],
+                        "prefs": { "download.default_directory": "/tmp/downloads" },
+                        "extensions": ["Q3IyNA=="],
+                        "mobileEmulation": {
+                            "deviceMetrics": {
+                                "width": 412,
+                                "height": 915,
+                                "pixelRatio": 2.625,
+                                "touch": true
+                            },
+                            "userAgent": "Mozilla/5.0 (Linux; Android 14)"
+                        },
+                        "perfLoggingPrefs": {
+                            "enableNetwork": true,
+                            "traceCategories": "devtools.timeline"
+                        },
+                        "excludeSwitches": ["enable-automation"]
+                    },
+                    "goog:loggingPrefs": { "browser": "ALL" }
+                }
+            }
+        }));
+    }
+
+    #[test]
+    fn empty_options_serialize_to_empty_object() {
+        assert_that!(serde_json::to_value(ChromeOptions::default()).unwrap())
+            .is_equal_to(json!({}));
+    }
+
+    #[test]
+    fn deserializes_unknown_options_into_extra() {
+        let options: ChromeOptions = serde_json::from_value(json!({
+            "binary": "/usr/bin/chrome",
+            "detach": true,
+            "mobileEmulation": { "deviceName": "Pixel 7" }
+        }))
+        .unwrap();
+
+        assert_that!(options.binary).is_equal_to(Some(PathBuf::from("/usr/bin/chrome")));
+        assert_that!(options.mobile_emulation.and_then(|it| it.device_name))
+            .is_equal_to(Some(String::from("Pixel 7")));
+        assert_that!(options.extra.get("detach").cloned()).is_equal_to(Some(json!(true)));
+    }
+
+    #[test]
+    fn with_args_renders_chrome_args() {
+        let options = ChromeOptions::default().with_args(
+            &ChromeArgs::new().headless().disable_gpu(),
+            Binary::ChromeHeadlessShell,
+        );
+
+        assert_that!(options.args).is_equal_to(vec![String::from("--disable-gpu")]);
+    }
+
+    #[test]
+    fn with_args_renders_for_given_binary() {
+        let args = ChromeArgs::new().headless().disable_gpu();
+
+        let shell = ChromeOptions::for_installation(
+            "/opt/cft",
+            Binary::ChromeHeadlessShell,
+            Platform::Linux64,
+        )
+        .with_args(&args, Binary::ChromeHeadlessShell);
+        let chrome = ChromeOptions::for_installation("/opt/cft", Binary::Chrome, Platform::Linux64)
+            .with_args(&args, Binary::Chrome);
+
+        assert_that!(shell.args).is_equal_to(vec![String::from("--disable-gpu")]);
+        assert_that!(chrome.args).is_equal_to(vec![
+            String::from("--headless=new"),
+            String::from("--disable-gpu"),
+        ]);
+    }
+}
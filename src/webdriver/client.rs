@@ -0,0 +1,394 @@
+use crate::api::endpoint::BODY_SNIPPET_LEN;
+use crate::diagnostics::KnownIssue;
+use crate::error::Error;
+use crate::webdriver::Capabilities;
+use rootcause::prelude::ResultExt;
+use rootcause::report;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use url::Url;
+
+/// A page whose title is checked by [`verify_install`].
+const VERIFICATION_PAGE: &str =
+    "data:text/html,%3Ctitle%3Echrome-for-testing%3C%2Ftitle%3E%3Cp%3EOK%3C%2Fp%3E";
+
+/// The title of [`VERIFICATION_PAGE`].
+const VERIFICATION_TITLE: &str = "chrome-for-testing";
+
+/// A minimal W3C WebDriver session, e.g. for smoke-testing an installation.
+///
+/// This is not meant to replace a full WebDriver client; it only covers the commands needed to
+/// prove that `ChromeDriver` and Chrome cooperate. The session is not deleted on drop; call
+/// [`WebDriverSession::delete`].
+#[derive(Debug, Clone)]
+pub struct WebDriverSession {
+    http: reqwest::Client,
+    base_url: Url,
+    id: String,
+    capabilities: Value,
+}
+
+impl WebDriverSession {
+    /// Creates a new session on the WebDriver server at `webdriver_url`, e.g. the URL of a
+    /// [`ChromeDriverProcess`](crate::chromedriver::ChromeDriverProcess).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the server refuses to create the session.
+    pub async fn new(
+        http: &reqwest::Client,
+        webdriver_url: &Url,
+        capabilities: &Capabilities,
+    ) -> crate::Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct NewSession {
+            session_id: String,
+            #[serde(default)]
+            capabilities: Value,
+        }
+
+        let mut base_url = webdriver_url.clone();
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        let url = base_url.join("session").context_to::<Error>()?;
+
+        let NewSession {
+            session_id,
+            capabilities,
+        } = send(
+            with_json(http.post(url), &capabilities.new_session_payload()),
+            "NewSession",
+        )
+        .await?;
+
+        Ok(Self {
+            http: http.clone(),
+            base_url,
+            id: session_id,
+            capabilities,
+        })
+    }
+
+    /// The session ID assigned by the server.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The capabilities the server matched, e.g. containing `browserVersion`.
+    #[must_use]
+    pub fn capabilities(&self) -> &Value {
+        &self.capabilities
+    }
+
+    /// Navigates the current top-level browsing context to `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the navigation is refused.
+    pub async fn navigate(&self, url: &str) -> crate::Result<()> {
+        send::<Value>(
+            with_json(self.http.post(self.url("/url")?), &json!({ "url": url })),
+            "NavigateTo",
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the title of the current page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn title(&self) -> crate::Result<String> {
+        send(self.http.get(self.url("/title")?), "GetTitle").await
+    }
+
+    /// Deletes the session, closing the browser.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn delete(self) -> crate::Result<()> {
+        send::<Value>(self.http.delete(self.url("")?), "DeleteSession").await?;
+        Ok(())
+    }
+
+    /// The URL of a command of this session, `suffix` being e.g. "/title".
+    fn url(&self, suffix: &str) -> crate::Result<Url> {
+        self.base_url
+            .join(&format!("session/{}{suffix}", self.id))
+            .context_to::<Error>()
+    }
+}
+
+/// The outcome of a successful [`verify_install`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallVerification {
+    /// The browser version reported by the session, if any.
+    pub browser_version: Option<String>,
+}
+
+/// Proves that the `ChromeDriver` at `webdriver_url` and the browser selected by `capabilities`
+/// cooperate: creates a session, loads a page, checks its title and deletes the session again.
+///
+/// ```no_run
+/// use chrome_for_testing::chromedriver::ChromeDriverLauncher;
+/// use chrome_for_testing::webdriver::{Capabilities, ChromeOptions, verify_install};
+/// use chrome_for_testing::{Binary, Platform};
+///
+/// # async fn example() -> chrome_for_testing::Result<()> {
+/// let platform = Platform::detect()?;
+/// let chromedriver = ChromeDriverLauncher::new("/opt/cft/chromedriver-linux64/chromedriver")
+///     .spawn()?;
+/// let capabilities = Capabilities::chrome(ChromeOptions::for_installation(
+///     "/opt/cft",
+///     Binary::ChromeHeadlessShell,
+///     platform,
+/// ));
+/// let verification =
+///     verify_install(&reqwest::Client::new(), chromedriver.url(), &capabilities).await?;
+/// println!("Verified Chrome {:?}", verification.browser_version);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if any WebDriver command fails, or [`Error::InstallVerification`] if the
+/// page title does not match.
+pub async fn verify_install(
+    http: &reqwest::Client,
+    webdriver_url: &Url,
+    capabilities: &Capabilities,
+) -> crate::Result<InstallVerification> {
+    let session = WebDriverSession::new(http, webdriver_url, capabilities).await?;
+
+    let checked = async {
+        session.navigate(VERIFICATION_PAGE).await?;
+        let title = session.title().await?;
+        if title != VERIFICATION_TITLE {
+            return Err(report!(Error::InstallVerification {
+                reason: format!("expected page title '{VERIFICATION_TITLE}', but got '{title}'"),
+            }));
+        }
+        Ok(())
+    }
+    .await;
+    let browser_version = session
+        .capabilities()
+        .get("browserVersion")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned);
+    let deleted = session.delete().await;
+
+    checked?;
+    deleted?;
+    Ok(InstallVerification { browser_version })
+}
+
+/// Sends a WebDriver command and unwraps the `value` of its response.
+async fn send<T: DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    command: &'static str,
+) -> crate::Result<T> {
+    #[derive(Deserialize)]
+    struct Response<T> {
+        value: T,
+    }
+
+    #[derive(Deserialize)]
+    struct ErrorValue {
+        error: String,
+        #[serde(default)]
+        message: String,
+    }
+
+    let response = request
+        .send()
+        .await
+        .context_to::<Error>()
+        .attach_with(|| format!("while sending WebDriver {command} command"))?;
+    let status = response.status();
+    let body = response
+        .bytes()
+        .await
+        .context_to::<Error>()
+        .attach_with(|| format!("while receiving WebDriver {command} response"))?;
+
+    if !status.is_success() {
+        let (error, message) = match serde_json::from_slice(&body) {
+            Ok(Response {
+                value: ErrorValue { error, message },
+            }) => (error, message),
+            Err(_) => (format!("HTTP status {}", status.as_u16()), snippet(&body)),
+        };
+        let issue = KnownIssue::detect(&message);
+        let mut report = report!(Error::WebDriver {
+            command,
+            error,
+            message,
+        });
+        if let Some(issue) = issue {
+            report = report.attach(issue);
+        }
+        return Err(report);
+    }
+
+    let mut deserializer = serde_json::Deserializer::from_slice(&body);
+    let response: Response<T> =
+        serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+            report!(Error::WebDriver {
+                command,
+                error: String::from("invalid response"),
+                message: format!("at '{}': {}", err.path(), err.inner()),
+            })
+            .attach(snippet(&body))
+        })?;
+    Ok(response.value)
+}
+
+fn with_json(request: reqwest::RequestBuilder, body: &Value) -> reqwest::RequestBuilder {
+    request
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+}
+
+fn snippet(body: &[u8]) -> String {
+    let snippet = &body[..body.len().min(BODY_SNIPPET_LEN)];
+    String::from_utf8_lossy(snippet).trim().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webdriver::ChromeOptions;
+    use assertr::prelude::*;
+    use mockito::Matcher;
+
+    async fn fake_webdriver_server(title: &str) -> (mockito::ServerGuard, Vec<mockito::Mock>) {
+        let mut server = mockito::Server::new_async().await;
+        let mocks = vec![
+            server
+                .mock("POST", "/session")
+                .match_body(Matcher::PartialJson(json!({
+                    "capabilities": { "alwaysMatch": { "browserName": "chrome" } }
+                })))
+                .with_header("content-type", "application/json")
+                .with_body(
+                    json!({
+                        "value": {
+                            "sessionId": "4f1c",
+                            "capabilities": { "browserName": "chrome", "browserVersion": "149.0.7789.0" }
+                        }
+                    })
+                    .to_string(),
+                )
+                .create_async()
+                .await,
+            server
+                .mock("POST", "/session/4f1c/url")
+                .match_body(Matcher::Json(json!({ "url": VERIFICATION_PAGE })))
+                .with_body(r#"{"value":null}"#)
+                .create_async()
+                .await,
+            server
+                .mock("GET", "/session/4f1c/title")
+                .with_body(json!({ "value": title }).to_string())
+                .create_async()
+                .await,
+            server
+                .mock("DELETE", "/session/4f1c")
+                .with_body(r#"{"value":null}"#)
+                .expect(1)
+                .create_async()
+                .await,
+        ];
+        (server, mocks)
+    }
+
+    fn capabilities() -> Capabilities {
+        Capabilities::chrome(ChromeOptions::default())
+    }
+
+    #[tokio::test]
+    async fn verify_install_runs_smoke_test_session() {
+        let (server, mocks) = fake_webdriver_server(VERIFICATION_TITLE).await;
+        let url: Url = server.url().parse().unwrap();
+
+        let verification = verify_install(&reqwest::Client::new(), &url, &capabilities())
+            .await
+            .unwrap();
+
+        assert_that!(verification.browser_version).is_equal_to(Some(String::from("149.0.7789.0")));
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_install_reports_title_mismatch_and_deletes_session() {
+        let (server, mocks) = fake_webdriver_server("Aw, Snap!").await;
+        let url: Url = server.url().parse().unwrap();
+
+        let err = verify_install(&reqwest::Client::new(), &url, &capabilities())
+            .await
+            .unwrap_err();
+
+        let Error::InstallVerification { reason } = err.current_context() else {
+            panic!(
+                "expected install verification error, got: {:?}",
+                err.current_context()
+            );
+        };
+
+        assert_that!(reason.as_str()).contains("Aw, Snap!");
+        mocks[3].assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn new_session_reports_webdriver_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/wd/hub/session")
+            .with_status(500)
+            .with_body(
+                json!({
+                    "value": {
+                        "error": "session not created",
+                        "message": "session not created: Chrome failed to start: crashed.",
+                        "stacktrace": ""
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let url: Url = format!("{}/wd/hub", server.url()).parse().unwrap();
+
+        let err = WebDriverSession::new(&reqwest::Client::new(), &url, &capabilities())
+            .await
+            .unwrap_err();
+
+        let Error::WebDriver {
+            command,
+            error,
+            message,
+        } = err.current_context()
+        else {
+            panic!("expected WebDriver error, got: {:?}", err.current_context());
+        };
+
+        assert_that!(*command).is_equal_to("NewSession");
+        assert_that!(error.as_str()).is_equal_to("session not created");
+        assert_that!(message.as_str()).starts_with("session not created: Chrome failed to start");
+        let issue = err
+            .attachments()
+            .iter()
+            .find_map(|attachment| attachment.downcast_inner::<KnownIssue>());
+        assert_that!(issue).is_equal_to(Some(&KnownIssue::BrowserCrashed));
+    }
+}
@@ -0,0 +1,15 @@
+mod capabilities;
+#[cfg(feature = "reqwest")]
+mod client;
+
+pub use capabilities::Capabilities;
+pub use capabilities::ChromeOptions;
+pub use capabilities::DeviceMetrics;
+pub use capabilities::MobileEmulation;
+pub use capabilities::PerfLoggingPrefs;
+#[cfg(feature = "reqwest")]
+pub use client::InstallVerification;
+#[cfg(feature = "reqwest")]
+pub use client::WebDriverSession;
+#[cfg(feature = "reqwest")]
+pub use client::verify_install;